@@ -0,0 +1,41 @@
+//! Captures the toolchain at build time, so stored timings describe the binaries that were benched.
+//! See `src/template/host.rs`.
+use std::{env, process::Command};
+
+fn main() {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let output = Command::new(rustc)
+        .arg("-vV")
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+        .unwrap_or_default();
+    let release = output
+        .lines()
+        .find_map(|l| l.strip_prefix("release:"))
+        .map_or("unknown", str::trim);
+
+    println!("cargo:rustc-env=AOC_RUSTC_VERSION={release}");
+    println!(
+        "cargo:rustc-env=AOC_TARGET={}",
+        env::var("TARGET").unwrap_or_else(|_| "unknown".into())
+    );
+    println!("cargo:rustc-env=AOC_FEATURES={}", features().join(","));
+    println!("cargo:rerun-if-changed=build.rs");
+}
+
+/// The enabled features. `CARGO_FEATURE_*` loses the spelling of a name (`dhat-heap` turns into `DHAT_HEAP`),
+/// so the list in `CARGO_CFG_FEATURE` is used where cargo provides it.
+fn features() -> Vec<String> {
+    let mut features: Vec<String> = match env::var("CARGO_CFG_FEATURE") {
+        Ok(list) => list
+            .split(',')
+            .filter(|f| !f.is_empty())
+            .map(String::from)
+            .collect(),
+        Err(_) => env::vars()
+            .filter_map(|(key, _)| Some(key.strip_prefix("CARGO_FEATURE_")?.to_lowercase()))
+            .collect(),
+    };
+    features.sort();
+    features
+}
@@ -1,35 +1,30 @@
-advent_of_code::solution!(1);
+advent_of_code::solution!(1, generator = generate);
 
-use nom::{
-    bytes::complete::take_while, character::complete::space1, combinator::map_res,
-    sequence::separated_pair, IResult,
-};
+use advent_of_code::parse::{number, parse_lines};
+use advent_of_code::testing::Rng;
+use nom::{character::complete::space1, sequence::separated_pair};
 use std::collections::HashMap;
 
-fn parse_line(input: &str) -> IResult<&str, (u32, u32)> {
-    separated_pair(
-        map_res(take_while(|c: char| c.is_ascii_digit()), str::parse),
-        space1,
-        map_res(take_while(|c: char| c.is_ascii_digit()), str::parse),
-    )(input)
+fn extract_lists(input: &str) -> Option<(Vec<u32>, Vec<u32>)> {
+    let pairs = parse_lines(input, separated_pair(number::<u32>, space1, number::<u32>)).ok()?;
+    Some(pairs.into_iter().unzip())
 }
 
-fn extract_lists(input: &str) -> (Vec<u32>, Vec<u32>) {
-    let mut left_list = Vec::new();
-    let mut right_list = Vec::new();
-
-    for line in input.lines() {
-        if let Ok((_, (left_num, right_num))) = parse_line(line) {
-            left_list.push(left_num);
-            right_list.push(right_num);
-        }
-    }
-
-    (left_list, right_list)
+/// Two lists with `lines` location ids each, see `cargo scale`.
+pub fn generate(rng: &mut Rng, lines: usize) -> String {
+    (0..lines)
+        .map(|_| {
+            format!(
+                "{}   {}\n",
+                rng.between(10_000, 99_999),
+                rng.between(10_000, 99_999)
+            )
+        })
+        .collect()
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let (mut left_list, mut right_list) = extract_lists(input);
+    let (mut left_list, mut right_list) = extract_lists(input)?;
 
     left_list.sort();
     right_list.sort();
@@ -37,20 +32,14 @@ pub fn part_one(input: &str) -> Option<u32> {
     let total_distance: u32 = left_list
         .iter()
         .zip(right_list.iter())
-        .map(|(left, right)| {
-            if left > right {
-                left - right
-            } else {
-                right - left
-            }
-        })
+        .map(|(left, right)| left.abs_diff(*right))
         .sum();
 
     Some(total_distance)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let (left_list, right_list) = extract_lists(input);
+    let (left_list, right_list) = extract_lists(input)?;
 
     let mut right_list_map = HashMap::new();
     for num in right_list.iter() {
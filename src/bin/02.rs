@@ -1,27 +1,10 @@
-advent_of_code::solution!(2);
+advent_of_code::solution!(2, generator = generate);
 
-use nom::{
-    character::complete::{digit1, space1},
-    combinator::map_res,
-    multi::separated_list1,
-    IResult,
-};
+use advent_of_code::parse::{number_list, parse_lines};
+use advent_of_code::testing::{format_numbers, numbers, Rng};
 
-fn parse_line(input: &str) -> IResult<&str, Vec<u32>> {
-    separated_list1(space1, map_res(digit1, str::parse))(input)
-}
-
-fn parse_input(input: &str) -> Vec<Vec<u32>> {
-    input
-        .lines()
-        .filter_map(|line| {
-            if let Ok((_, parsed_line)) = parse_line(line) {
-                Some(parsed_line)
-            } else {
-                None
-            }
-        })
-        .collect()
+fn parse_input(input: &str) -> Option<Vec<Vec<u32>>> {
+    parse_lines(input, number_list(" ")).ok()
 }
 
 fn is_valid_line(line: &[u32]) -> bool {
@@ -46,8 +29,18 @@ fn is_valid_line(line: &[u32]) -> bool {
     valid && (increasing || decreasing)
 }
 
+/// `reports` reports of five to eight levels each, see `cargo scale`.
+pub fn generate(rng: &mut Rng, reports: usize) -> String {
+    (0..reports)
+        .map(|_| {
+            let levels = rng.between(5, 8) as usize;
+            format_numbers(&numbers(rng, levels, 99), " ")
+        })
+        .collect()
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
-    let report = parse_input(input);
+    let report = parse_input(input)?;
     let mut safe_lines = Vec::new();
 
     for line in report.iter() {
@@ -60,7 +53,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let report = parse_input(input);
+    let report = parse_input(input)?;
     let mut safe_lines = Vec::new();
 
     for line in report.iter() {
@@ -1,7 +1,32 @@
-advent_of_code::solution!(3);
+advent_of_code::solution!(3, generator = generate);
 
+use advent_of_code::testing::Rng;
 use regex::Regex;
 
+/// Corrupted memory with `instructions` instructions between some noise, see `cargo scale`.
+pub fn generate(rng: &mut Rng, instructions: usize) -> String {
+    let noise = [
+        "x",
+        "%&",
+        "!@^",
+        "mul[3,7]",
+        "+mul(32,64]",
+        "then(",
+        "why()",
+        "do_not_",
+    ];
+    (0..instructions)
+        .map(|_| {
+            let instruction = match rng.below(10) {
+                0 => "do()".to_string(),
+                1 => "don't()".to_string(),
+                _ => format!("mul({},{})", rng.between(1, 999), rng.between(1, 999)),
+            };
+            format!("{}{instruction}", rng.pick(&noise))
+        })
+        .collect()
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
     let re = Regex::new(r"mul\((\d+),(\d+)\)").unwrap();
     let mut results = Vec::new();
@@ -1,47 +1,26 @@
-advent_of_code::solution!(5);
-
-use nom::{
-    bytes::complete::tag,
-    character::complete::{digit1, line_ending},
-    combinator::map_res,
-    multi::separated_list1,
-    sequence::separated_pair,
-    IResult,
-};
+advent_of_code::solution!(5, generator = generate);
+
+use advent_of_code::graph::{topological_sort, Cycle};
+use advent_of_code::parse::{number, number_list, paragraphs, parse_lines};
+use advent_of_code::testing::{format_numbers, Rng};
+use nom::{bytes::complete::tag, sequence::separated_pair};
 use rayon::prelude::*;
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::HashMap;
 
 type Rule = (u32, u32);
 type Update = Vec<u32>;
 type ParsedInput = (Vec<Rule>, Vec<Update>);
 type UpdateWithRules = (Update, Vec<Rule>);
 
-fn parse_rule(input: &str) -> IResult<&str, Rule> {
-    separated_pair(
-        map_res(digit1, str::parse),
-        tag("|"),
-        map_res(digit1, str::parse),
-    )(input)
-}
-
-fn parse_rules(input: &str) -> IResult<&str, Vec<Rule>> {
-    separated_list1(line_ending, parse_rule)(input)
-}
-
-fn parse_update(input: &str) -> IResult<&str, Update> {
-    separated_list1(tag(","), map_res(digit1, str::parse))(input)
-}
-
-fn parse_input(input: &str) -> IResult<&str, ParsedInput> {
-    let (input, rules) = parse_rules(input)?;
-    let (input, _) = line_ending(input)?;
-    let (input, _) = line_ending(input)?;
-    let (input, updates) = separated_list1(line_ending, parse_update)(input)?;
-    Ok((input, (rules, updates)))
+fn parse_input(input: &str) -> Option<ParsedInput> {
+    let mut blocks = paragraphs(input);
+    let rules = parse_lines(blocks.next()?, separated_pair(number, tag("|"), number)).ok()?;
+    let updates = parse_lines(blocks.next()?, number_list(",")).ok()?;
+    Some((rules, updates))
 }
 
 fn find_updates(input: &str) -> Option<(Vec<UpdateWithRules>, Vec<UpdateWithRules>)> {
-    let (_, (rules, updates)) = parse_input(input).ok()?;
+    let (rules, updates) = parse_input(input)?;
 
     // Use Rayon to parallelize the processing of updates
     let (valid_updates, invalid_updates): (Vec<_>, Vec<_>) = updates
@@ -82,6 +61,29 @@ fn find_updates(input: &str) -> Option<(Vec<UpdateWithRules>, Vec<UpdateWithRule
     ))
 }
 
+/// `updates` updates of an odd number of pages, see `cargo scale`.
+/// The pages are in a random order, with a rule for every pair of them.
+pub fn generate(rng: &mut Rng, updates: usize) -> String {
+    let mut pages: Vec<u64> = (10..60).collect();
+    pages.sort_by_cached_key(|_| rng.next_u64());
+
+    let mut text = String::new();
+    for (i, before) in pages.iter().enumerate() {
+        for after in &pages[i + 1..] {
+            text += &format!("{before}|{after}\n");
+        }
+    }
+    text.push('\n');
+
+    for _ in 0..updates {
+        let len = 2 * rng.between(2, 11) as usize + 1;
+        let mut update = pages.clone();
+        update.sort_by_cached_key(|_| rng.next_u64());
+        text += &format_numbers(&update[..len], ",");
+    }
+    text
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
     let (valid_updates, _) = find_updates(input)?;
 
@@ -100,72 +102,24 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(result)
 }
 
-fn reorder_update(update: &[u32], rules: &[Rule]) -> Vec<u32> {
-    // Create a graph of dependencies
-    let mut graph: HashMap<u32, Vec<u32>> = HashMap::new();
-    let mut in_degree: HashMap<u32, usize> = HashMap::new();
-    let mut nodes: HashSet<u32> = HashSet::new();
-
-    // Build the graph and collect nodes
-    for &num in update {
-        nodes.insert(num);
-        graph.entry(num).or_default();
-        in_degree.entry(num).or_insert(0);
-    }
-
-    // Add edges based on rules
-    for &(before, after) in rules {
-        if nodes.contains(&before) && nodes.contains(&after) {
-            graph.entry(before).or_default().push(after);
-            *in_degree.entry(after).or_default() += 1;
-        }
-    }
-
-    // Topological sort using Kahn's algorithm
-    let mut result = Vec::new();
-    let mut queue: VecDeque<u32> = in_degree
-        .iter()
-        .filter(|&(_, &count)| count == 0)
-        .map(|(&node, _)| node)
-        .collect();
-
-    while let Some(node) = queue.pop_front() {
-        result.push(node);
-
-        if let Some(neighbors) = graph.get(&node) {
-            for &next in neighbors {
-                *in_degree.get_mut(&next).unwrap() -= 1;
-                if in_degree[&next] == 0 {
-                    queue.push_back(next);
-                }
-            }
-        }
-    }
-
-    // If we couldn't order all nodes, preserve original order for remaining nodes
-    for &num in update {
-        if !result.contains(&num) {
-            result.push(num);
-        }
-    }
-
-    result
+fn reorder_update(update: &[u32], rules: &[Rule]) -> Result<Vec<u32>, Cycle<u32>> {
+    // Every rule is an edge from the page that comes first to the page after it
+    topological_sort(update.iter().copied(), |&page| {
+        rules
+            .iter()
+            .filter(move |&&(before, _)| before == page)
+            .map(|&(_, after)| after)
+    })
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
     let (_, invalid_updates) = find_updates(input)?;
 
+    // An update whose rules contradict each other has no correct order, so it is skipped
     let result = invalid_updates
         .par_iter()
-        .map(|(update, rules)| {
-            let reordered = reorder_update(update, rules);
-            if !reordered.is_empty() {
-                let center_index = reordered.len() / 2;
-                reordered[center_index]
-            } else {
-                0
-            }
-        })
+        .filter_map(|(update, rules)| reorder_update(update, rules).ok())
+        .filter_map(|update| update.get(update.len() / 2).copied())
         .sum();
 
     Some(result)
@@ -186,4 +140,10 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(123));
     }
+
+    #[test]
+    fn test_part_two_skips_cyclic_updates() {
+        let input = "1|2\n2|3\n3|1\n4|5\n5|6\n\n1,2,3\n6,5,4\n";
+        assert_eq!(part_two(input), Some(5));
+    }
 }
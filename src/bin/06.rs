@@ -1,14 +1,12 @@
-advent_of_code::solution!(6);
-
-use nom::{
-    character::complete::{char, line_ending},
-    combinator::map,
-    multi::many1,
-    sequence::terminated,
-    IResult,
-};
+advent_of_code::solution!(6, part_two = [part_two_brent], generator = generate);
+
+use advent_of_code::bitset::{BitGrid, DenseSet};
+use advent_of_code::cycle::{brent, trace};
+use advent_of_code::geometry::Direction;
+use advent_of_code::grid::Grid;
+use advent_of_code::testing::{format_grid, grid, Rng};
 use rayon::prelude::*;
-use std::collections::HashSet;
+use std::fmt::Display;
 
 #[derive(Debug, PartialEq, Clone)]
 enum Cell {
@@ -18,158 +16,140 @@ enum Cell {
     GuardVisited(Direction),
 }
 
-fn parse_cell(input: &str) -> IResult<&str, Cell> {
-    let (input, cell) = nom::branch::alt((
-        map(char('.'), |_| Cell::Empty),
-        map(char('#'), |_| Cell::Obstruction),
-        map(char('^'), |_| Cell::Guard),
-    ))(input)?;
-    Ok((input, cell))
-}
-
-fn parse_row(input: &str) -> IResult<&str, Vec<Cell>> {
-    many1(parse_cell)(input)
-}
-
-fn parse_map(input: &str) -> IResult<&str, Vec<Vec<Cell>>> {
-    many1(terminated(parse_row, line_ending))(input)
+impl From<char> for Cell {
+    fn from(c: char) -> Self {
+        match c {
+            '#' => Cell::Obstruction,
+            '^' => Cell::Guard,
+            _ => Cell::Empty,
+        }
+    }
 }
 
-#[allow(dead_code)]
-fn print_map(map: Vec<Vec<Cell>>) {
-    for row in map {
-        for cell in row {
-            match cell {
-                Cell::Empty => print!("."),
-                Cell::Obstruction => print!("#"),
-                Cell::Guard => print!("o"),
-                Cell::GuardVisited(Up) => print!("^"),
-                Cell::GuardVisited(Right) => print!(">"),
-                Cell::GuardVisited(Down) => print!("v"),
-                Cell::GuardVisited(Left) => print!("<"),
-            }
+impl Display for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Cell::Empty => write!(f, "."),
+            Cell::Obstruction => write!(f, "#"),
+            Cell::Guard => write!(f, "o"),
+            Cell::GuardVisited(direction) => write!(f, "{}", direction.arrow()),
         }
-        println!();
     }
 }
 
+/// A square lab with a side of `size` positions and the guard in its center, see `cargo scale`.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut lab = grid(rng, size, size, ".........#");
+    lab[size / 2][size / 2] = '^';
+    format_grid(&lab)
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
-    let (_, mut map) = parse_map(input).unwrap();
+    let mut map = Grid::parse(input, Cell::from).ok()?;
     predict_guard_movement(&mut map);
-    let mut visited_count = 0;
-    for row in &map {
-        for cell in row {
-            if let Cell::GuardVisited(_) = cell {
-                visited_count += 1;
-            }
-        }
-    }
-    Some(visited_count)
+    let visited_count = map
+        .iter()
+        .filter(|cell| matches!(cell, Cell::GuardVisited(_)))
+        .count();
+    Some(visited_count as u32)
 }
-#[derive(Debug, PartialEq, Clone, Copy, Hash, Eq)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
+
+/// The guard's position and facing.
+type Guard = ((usize, usize), Direction);
+
+fn find_guard(map: &Grid<Cell>) -> Option<Guard> {
+    map.find(&Cell::Guard)
+        .map(|position| (position, Direction::Up))
 }
-use Direction::{Down, Left, Right, Up};
-
-fn find_guard_position(map: &[Vec<Cell>]) -> Option<(usize, usize)> {
-    for (i, row) in map.iter().enumerate() {
-        for (j, cell) in row.iter().enumerate() {
-            if *cell == Cell::Guard {
-                return Some((i, j));
-            }
-        }
+
+/// The guard steps forward or turns right in front of an obstruction. `None` once they leave the map.
+fn step(map: &Grid<Cell>, &(position, dir): &Guard) -> Option<Guard> {
+    let new_position = map.offset(position, dir.delta().into())?;
+    match map[new_position] {
+        Cell::Obstruction => Some((position, dir.turn_right())),
+        _ => Some((new_position, dir)),
     }
-    None
 }
 
-fn predict_guard_movement(map: &mut [Vec<Cell>]) -> bool {
-    let directions = [
-        (Up, (-1, 0)),
-        (Right, (0, 1)),
-        (Down, (1, 0)),
-        (Left, (0, -1)),
-    ];
-    let mut direction_index = 0;
-    let mut visited_positions = HashSet::new();
-
-    if let Some((mut x, mut y)) = find_guard_position(map) {
-        loop {
-            let (dir, (dx, dy)) = directions[direction_index];
-            let (new_x, new_y) = (x as isize + dx, y as isize + dy);
-
-            // Mark the current cell as visited
-            visited_positions.insert((x as isize, y as isize, dir));
-            map[x][y] = Cell::GuardVisited(dir);
-
-            // Check if the new position is out of bounds
-            if new_x < 0
-                || new_x >= map.len() as isize
-                || new_y < 0
-                || new_y >= map[0].len() as isize
-            {
-                return false;
-            }
-
-            // Check for loop detection
-            if visited_positions.contains(&(new_x, new_y, dir)) {
-                return true;
-            }
-
-            match map[new_x as usize][new_y as usize] {
-                Cell::Obstruction => {
-                    direction_index = (direction_index + 1) % 4; // Turn right 90 degrees
-                }
-                _ => {
-                    x = new_x as usize;
-                    y = new_y as usize;
-                }
-            }
-        }
-    } else {
-        false
+/// Marks every cell the guard visits and returns whether they end up walking in a loop.
+fn predict_guard_movement(map: &mut Grid<Cell>) -> bool {
+    let Some(guard) = find_guard(map) else {
+        return false;
+    };
+
+    let path = trace(guard, |guard| step(map, guard));
+    for &(position, dir) in &path.states {
+        map[position] = Cell::GuardVisited(dir);
     }
+    path.cycle.is_some()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (_, map) = parse_map(input).unwrap();
-    let guard_position = find_guard_position(&map);
+/// Whether the guard ends up walking in a loop, recording their states in `seen`.
+fn walks_in_loop(map: &Grid<Cell>, guard: Guard, seen: &mut DenseSet<Guard>) -> bool {
+    seen.clear();
+    let mut guard = Some(guard);
+    while let Some(current) = guard {
+        if !seen.insert(current) {
+            return true;
+        }
+        guard = step(map, &current);
+    }
+    false
+}
 
-    // find all visited positions, excluding the guard's initial position
+/// Positions to try an obstruction at: all visited positions, excluding the guard's initial position.
+fn obstruction_candidates(map: &Grid<Cell>, guard: Guard) -> Vec<(usize, usize)> {
     let mut visited = map.clone();
     predict_guard_movement(&mut visited);
-    let mut visited_positions: HashSet<(usize, usize)> = HashSet::new();
-    for (i, row) in visited.iter().enumerate() {
-        for (j, cell) in row.iter().enumerate() {
-            if let Cell::GuardVisited(_) = cell {
-                visited_positions.insert((i, j));
-            }
+
+    let mut candidates = BitGrid::for_grid(map);
+    for (pos, cell) in visited.enumerate() {
+        if matches!(cell, Cell::GuardVisited(_)) {
+            candidates.insert(pos);
         }
     }
-    if let Some((guard_x, guard_y)) = guard_position {
-        visited_positions.remove(&(guard_x, guard_y));
-    }
+    candidates.remove(guard.0);
+    candidates.iter().collect()
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    let map = Grid::parse(input, Cell::from).ok()?;
+    let guard = find_guard(&map)?;
 
-    // try putting an obstruction everywhere along the path and count loops
-    let loop_count: u32 = visited_positions
+    // try putting an obstruction everywhere along the path and count loops, every thread reuses one set of states
+    let loop_count = obstruction_candidates(&map, guard)
         .par_iter()
-        .filter_map(|&(i, j)| {
-            if let Cell::Empty = map[i][j] {
-                let mut new_map = map.clone();
-                new_map[i][j] = Cell::Obstruction;
-
-                if predict_guard_movement(&mut new_map) {
-                    return Some(1);
-                }
-            }
-            None
+        .map_init(
+            || (map.clone(), DenseSet::for_grid(&map)),
+            |(new_map, seen), &pos| {
+                new_map[pos] = Cell::Obstruction;
+                let loops = walks_in_loop(new_map, guard, seen);
+                new_map[pos] = Cell::Empty;
+                loops
+            },
+        )
+        .filter(|&loops| loops)
+        .count();
+
+    Some(loop_count as u32)
+}
+
+pub fn part_two_brent(input: &str) -> Option<u32> {
+    let map = Grid::parse(input, Cell::from).ok()?;
+    let guard = find_guard(&map)?;
+
+    let loop_count = obstruction_candidates(&map, guard)
+        .par_iter()
+        .filter(|&&pos| {
+            let mut new_map = map.clone();
+            new_map[pos] = Cell::Obstruction;
+
+            // Brent's algorithm needs no set of visited states for every obstruction
+            brent(&guard, |guard| step(&new_map, guard)).is_some()
         })
-        .sum();
+        .count();
 
-    Some(loop_count)
+    Some(loop_count as u32)
 }
 
 #[cfg(test)]
@@ -187,4 +167,10 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(6));
     }
+
+    #[test]
+    fn test_part_two_brent() {
+        let result = part_two_brent(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(6));
+    }
 }
@@ -1,22 +1,22 @@
-advent_of_code::solution!(7);
+advent_of_code::solution!(7, generator = generate);
 
-use nom::{
-    bytes::complete::tag,
-    character::complete::{digit1, space1},
-    combinator::map_res,
-    multi::separated_list1,
-    sequence::separated_pair,
-    IResult,
-};
+use advent_of_code::parse::{key_value, number, number_list, parse_lines};
+use advent_of_code::testing::{equation, format_equations, Rng};
 use rayon::prelude::*;
 
-fn parse_line(input: &str) -> IResult<&str, (u64, Vec<u64>)> {
-    let (input, (test_value, numbers)) = separated_pair(
-        map_res(digit1, str::parse),
-        tag(": "),
-        separated_list1(space1, map_res(digit1, str::parse)),
-    )(input)?;
-    Ok((input, (test_value, numbers)))
+fn parse_input(input: &str) -> Option<Vec<(u64, Vec<u64>)>> {
+    parse_lines(input, key_value(number, ":", number_list(" "))).ok()
+}
+
+/// `lines` calibration equations of two to eight numbers each, see `cargo scale`.
+pub fn generate(rng: &mut Rng, lines: usize) -> String {
+    let equations: Vec<_> = (0..lines)
+        .map(|_| {
+            let len = rng.between(2, 8) as usize;
+            equation(rng, len, 99)
+        })
+        .collect();
+    format_equations(&equations)
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -84,10 +84,7 @@ fn find_matching_expression(
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let parsed_lines: Vec<(u64, Vec<u64>)> = input
-        .lines()
-        .map(|line| parse_line(line).unwrap().1)
-        .collect();
+    let parsed_lines = parse_input(input)?;
 
     let result = parsed_lines
         .par_iter()
@@ -103,10 +100,7 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let parsed_lines: Vec<(u64, Vec<u64>)> = input
-        .lines()
-        .map(|line| parse_line(line).unwrap().1)
-        .collect();
+    let parsed_lines = parse_input(input)?;
 
     let result = parsed_lines
         .par_iter()
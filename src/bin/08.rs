@@ -1,13 +1,16 @@
-advent_of_code::solution!(8);
-
-use nom::{
-    character::complete::{char, one_of},
-    combinator::map,
-    multi::many1,
-    sequence::terminated,
-    IResult,
-};
+advent_of_code::solution!(8, generator = generate);
+
+use advent_of_code::bitset::BitGrid;
+use advent_of_code::grid::Grid;
+use advent_of_code::testing::{format_grid, grid, Rng};
 use std::collections::HashSet;
+use std::fmt::Display;
+
+/// A square map with a side of `size` positions and antennas of eight frequencies, see `cargo scale`.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let cells = ".".repeat(92) + "0123aAbB";
+    format_grid(&grid(rng, size, size, &cells))
+}
 
 #[derive(Debug, PartialEq)]
 enum Cell {
@@ -15,85 +18,43 @@ enum Cell {
     Empty,
 }
 
-fn parse_grid(input: &str) -> IResult<&str, Vec<Vec<Cell>>> {
-    many1(terminated(
-        many1(map(
-            one_of("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789."),
-            |c| {
-                if c == '.' {
-                    Cell::Empty
-                } else {
-                    Cell::Antenna(c)
-                }
-            },
-        )),
-        char('\n'),
-    ))(input)
+impl From<char> for Cell {
+    fn from(c: char) -> Self {
+        if c == '.' {
+            Cell::Empty
+        } else {
+            Cell::Antenna(c)
+        }
+    }
 }
 
-#[allow(dead_code)]
-fn print_grid(grid: &[Vec<Cell>], antinodes: &HashSet<(usize, usize)>) {
-    for (i, row) in grid.iter().enumerate() {
-        for (j, cell) in row.iter().enumerate() {
-            if antinodes.contains(&(i, j)) {
-                print!("#");
-            } else {
-                match cell {
-                    Cell::Empty => print!("."),
-                    Cell::Antenna(c) => print!("{}", c),
-                }
-            }
+impl Display for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Cell::Empty => write!(f, "."),
+            Cell::Antenna(c) => write!(f, "{c}"),
         }
-        println!();
     }
 }
 
 fn calculate_antinodes(
-    grid: &[Vec<Cell>],
+    grid: &Grid<Cell>,
     antenna_type: char,
     resonant: bool,
 ) -> Vec<(usize, usize)> {
+    let antennas: Vec<(usize, usize)> = grid.find_all(&Cell::Antenna(antenna_type)).collect();
     let mut antinodes = Vec::new();
-    let rows = grid.len();
-    let cols = grid[0].len();
 
-    for (i, row) in grid.iter().enumerate() {
-        for (j, cell) in row.iter().enumerate() {
-            if let Cell::Antenna(c) = cell {
-                if resonant {
-                    antinodes.push((i, j));
-                }
-                if *c == antenna_type {
-                    for (k, other_row) in grid.iter().enumerate() {
-                        for (l, other_cell) in other_row.iter().enumerate() {
-                            if i != k || j != l {
-                                if let Cell::Antenna(other_c) = other_cell {
-                                    if *other_c == antenna_type {
-                                        let mut antinode_row =
-                                            i as isize + (i as isize - k as isize);
-                                        let mut antinode_col =
-                                            j as isize + (j as isize - l as isize);
-                                        while antinode_row >= 0
-                                            && antinode_col >= 0
-                                            && antinode_row < rows as isize
-                                            && antinode_col < cols as isize
-                                        {
-                                            antinodes.push((
-                                                antinode_row as usize,
-                                                antinode_col as usize,
-                                            ));
-                                            if !resonant {
-                                                break;
-                                            }
-                                            antinode_row += i as isize - k as isize;
-                                            antinode_col += j as isize - l as isize;
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+    for &(i, j) in &antennas {
+        if resonant {
+            antinodes.push((i, j));
+        }
+        for &(k, l) in &antennas {
+            if (i, j) != (k, l) {
+                let delta = (i as isize - k as isize, j as isize - l as isize);
+                // The first antinode is one step away from the antenna, resonant ones repeat until the edge
+                let ray = grid.ray((i, j), delta).skip(1);
+                antinodes.extend(ray.take(if resonant { usize::MAX } else { 1 }));
             }
         }
     }
@@ -101,13 +62,11 @@ fn calculate_antinodes(
     antinodes
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (_, grid) = parse_grid(input).unwrap();
+fn count_antinodes(input: &str, resonant: bool) -> Option<u32> {
+    let grid = Grid::parse(input, Cell::from).ok()?;
 
-    let mut antinodes_set = HashSet::new();
     let antenna_types: HashSet<char> = grid
         .iter()
-        .flat_map(|row| row.iter())
         .filter_map(|cell| {
             if let Cell::Antenna(c) = cell {
                 Some(*c)
@@ -117,9 +76,9 @@ pub fn part_one(input: &str) -> Option<u32> {
         })
         .collect();
 
+    let mut antinodes_set = BitGrid::for_grid(&grid);
     for antenna_type in antenna_types {
-        let antinodes = calculate_antinodes(&grid, antenna_type, false);
-        for antinode in antinodes {
+        for antinode in calculate_antinodes(&grid, antenna_type, resonant) {
             antinodes_set.insert(antinode);
         }
     }
@@ -127,30 +86,12 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(antinodes_set.len() as u32)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (_, grid) = parse_grid(input).unwrap();
-
-    let mut antinodes_set = HashSet::new();
-    let antenna_types: HashSet<char> = grid
-        .iter()
-        .flat_map(|row| row.iter())
-        .filter_map(|cell| {
-            if let Cell::Antenna(c) = cell {
-                Some(*c)
-            } else {
-                None
-            }
-        })
-        .collect();
-
-    for antenna_type in antenna_types {
-        let antinodes = calculate_antinodes(&grid, antenna_type, true);
-        for antinode in antinodes {
-            antinodes_set.insert(antinode);
-        }
-    }
+pub fn part_one(input: &str) -> Option<u32> {
+    count_antinodes(input, false)
+}
 
-    Some((antinodes_set.len()) as u32)
+pub fn part_two(input: &str) -> Option<u32> {
+    count_antinodes(input, true)
 }
 
 #[cfg(test)]
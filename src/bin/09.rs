@@ -1,4 +1,12 @@
-advent_of_code::solution!(9);
+advent_of_code::solution!(
+    9,
+    part_two = [part_two_spans, part_two_windows],
+    generator = generate
+);
+
+use advent_of_code::ranges::{IntervalMap, RangeSet};
+use advent_of_code::testing::{disk_map, format_disk_map, Rng};
+use std::ops::Range;
 
 pub fn parse_disk_map(input: &str) -> Vec<Option<u64>> {
     let mut result = Vec::new();
@@ -15,9 +23,9 @@ pub fn parse_disk_map(input: &str) -> Vec<Option<u64>> {
         let file_length = file_length_char.to_digit(10).unwrap_or(0);
 
         // Append file blocks
-        result.extend(std::iter::repeat(Some(file_id)).take(file_length as usize));
+        result.extend(std::iter::repeat_n(Some(file_id), file_length as usize));
         // Append free space blocks
-        result.extend(std::iter::repeat(None).take(free_space_length as usize));
+        result.extend(std::iter::repeat_n(None, free_space_length as usize));
 
         file_id += 1;
     }
@@ -78,7 +86,7 @@ pub fn compact_disk_map(mut disk_map: Vec<Option<u64>>) -> Vec<Option<u64>> {
     }
 
     // Sort files in decreasing order of file_id
-    files.sort_by(|a, b| b.0.cmp(&a.0));
+    files.sort_by_key(|file| std::cmp::Reverse(file.0));
 
     for (file_id, start, size) in files {
         // Find the leftmost span of free space that can fit the file
@@ -97,6 +105,85 @@ pub fn compact_disk_map(mut disk_map: Vec<Option<u64>>) -> Vec<Option<u64>> {
     disk_map
 }
 
+/// The files of a disk map as spans of blocks with their id.
+pub fn parse_files(input: &str) -> IntervalMap<usize, u64> {
+    let mut files = IntervalMap::new();
+    let mut position = 0;
+
+    for (i, c) in input.trim().chars().enumerate() {
+        let length = c.to_digit(10).unwrap_or(0) as usize;
+        // Even digits are files, odd digits free space
+        if i % 2 == 0 {
+            files.insert(position..position + length, (i / 2) as u64);
+        }
+        position += length;
+    }
+
+    files
+}
+
+/// Moves every file, in decreasing order of file id, to the leftmost span of free space that fits it.
+pub fn compact_files(files: &mut IntervalMap<usize, u64>) {
+    let end = files.iter().last().map_or(0, |(span, _)| span.end);
+    let mut free: RangeSet<usize> = files.gaps(0..end).collect();
+
+    // Files are in increasing order of file id on the disk
+    let moves: Vec<(Range<usize>, u64)> = files.iter().map(|(span, &id)| (span, id)).collect();
+    for (span, file_id) in moves.into_iter().rev() {
+        // Only move the file if the free space is to the left of its current position
+        let Some(target) = free
+            .first_fit(span.len())
+            .filter(|target| target.start < span.start)
+        else {
+            continue;
+        };
+
+        files.remove(span.clone());
+        files.insert(target.clone(), file_id);
+        free.remove(target);
+        free.insert(span);
+    }
+}
+
+/// Moves whole files like `compact_disk_map`, but keeps a list of free spans instead of scanning the disk.
+/// Space freed by a moved file is never reused, since all remaining files are left of it.
+pub fn compact_disk_map_spans(mut disk_map: Vec<Option<u64>>) -> Vec<Option<u64>> {
+    let mut files: Vec<(u64, usize, usize)> = Vec::new();
+    let mut spans: Vec<(usize, usize)> = Vec::new();
+
+    let mut i = 0;
+    while i < disk_map.len() {
+        let start = i;
+        while i < disk_map.len() && disk_map[i] == disk_map[start] {
+            i += 1;
+        }
+        match disk_map[start] {
+            Some(file_id) => files.push((file_id, start, i - start)),
+            None => spans.push((start, i - start)),
+        }
+    }
+
+    files.sort_by_key(|file| std::cmp::Reverse(file.0));
+
+    for (file_id, start, size) in files {
+        let target = spans
+            .iter_mut()
+            .take_while(|(span_start, _)| *span_start < start)
+            .find(|(_, span_size)| *span_size >= size);
+
+        if let Some((span_start, span_size)) = target {
+            for j in 0..size {
+                disk_map[*span_start + j] = Some(file_id);
+                disk_map[start + j] = None;
+            }
+            *span_start += size;
+            *span_size -= size;
+        }
+    }
+
+    disk_map
+}
+
 // Helper function to find the leftmost span of free space that can fit the file
 fn find_leftmost_fit(disk_map: &[Option<u64>], size: usize) -> Option<usize> {
     disk_map
@@ -112,6 +199,18 @@ pub fn checksum(disk_map: &[Option<u64>]) -> u64 {
         .sum()
 }
 
+pub fn checksum_files(files: &IntervalMap<usize, u64>) -> u64 {
+    files
+        .iter()
+        .map(|(span, &id)| span.map(|pos| pos as u64 * id).sum::<u64>())
+        .sum()
+}
+
+/// A disk map with `files` files, see `cargo scale`.
+pub fn generate(rng: &mut Rng, files: usize) -> String {
+    format_disk_map(&disk_map(rng, files))
+}
+
 pub fn part_one(input: &str) -> Option<u64> {
     let disk_map = parse_disk_map(input);
     let compacted_map = compact_disk_map_naive(disk_map);
@@ -119,14 +218,28 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
+    let mut files = parse_files(input);
+    compact_files(&mut files);
+    Some(checksum_files(&files))
+}
+
+/// The block-level window scan, kept as the reference for the other implementations.
+pub fn part_two_windows(input: &str) -> Option<u64> {
     let disk_map = parse_disk_map(input);
     let compacted_map = compact_disk_map(disk_map);
     Some(checksum(&compacted_map))
 }
 
+pub fn part_two_spans(input: &str) -> Option<u64> {
+    let disk_map = parse_disk_map(input);
+    let compacted_map = compact_disk_map_spans(disk_map);
+    Some(checksum(&compacted_map))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::testing::{cross_check, shrink_disk_map};
 
     #[test]
     fn test_part_one() {
@@ -139,4 +252,36 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(2858));
     }
+
+    #[test]
+    fn test_part_two_spans() {
+        let result = part_two_spans(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(2858));
+    }
+
+    #[test]
+    fn test_part_two_windows() {
+        let result = part_two_windows(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(2858));
+    }
+
+    #[test]
+    fn cross_check_part_two() {
+        cross_check(
+            disk_map,
+            |digits| shrink_disk_map(digits),
+            |digits| part_two_windows(&format_disk_map(digits)),
+            |digits| part_two(&format_disk_map(digits)),
+        );
+    }
+
+    #[test]
+    fn cross_check_part_two_spans() {
+        cross_check(
+            disk_map,
+            |digits| shrink_disk_map(digits),
+            |digits| part_two_windows(&format_disk_map(digits)),
+            |digits| part_two_spans(&format_disk_map(digits)),
+        );
+    }
 }
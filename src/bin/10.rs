@@ -1,14 +1,24 @@
-advent_of_code::solution!(10);
-
-use nom::{
-    character::complete::{line_ending, one_of, space0},
-    combinator::map,
-    multi::{many1, separated_list1},
-    sequence::preceded,
-    IResult,
-};
-
-use std::collections::HashSet;
+advent_of_code::solution!(10, generator = generate);
+
+use advent_of_code::bitset::BitGrid;
+use advent_of_code::graph::{count_paths, dfs_with};
+use advent_of_code::grid::Grid;
+use advent_of_code::testing::{format_grid, grid, Rng};
+use std::fmt::Display;
+
+/// A square map with a side of `size` positions, see `cargo scale`.
+/// Most heights climb to the right and down, so that the map has trails.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut map = grid(rng, size, size, "0123456789");
+    for (y, row) in map.iter_mut().enumerate() {
+        for (x, height) in row.iter_mut().enumerate() {
+            if rng.chance(3, 4) {
+                *height = char::from(b'0' + ((x + y) % 10) as u8);
+            }
+        }
+    }
+    format_grid(&map)
+}
 
 #[derive(Debug, Clone, Copy)]
 #[repr(u8)]
@@ -17,134 +27,71 @@ enum Cell {
     Empty,
 }
 
-fn parse_input(input: &str) -> IResult<&str, Vec<Vec<Cell>>> {
-    separated_list1(line_ending, parse_line)(input)
-}
-
-fn parse_line(input: &str) -> IResult<&str, Vec<Cell>> {
-    preceded(space0, many1(parse_cell))(input)
-}
-
-fn parse_cell(input: &str) -> IResult<&str, Cell> {
-    map(one_of("0123456789."), |c| match c {
-        '.' => Cell::Empty,
-        d => Cell::Height(d.to_digit(10).unwrap() as u8),
-    })(input)
-}
-
-#[derive(Debug)]
-struct Trail {
-    path: Vec<(usize, usize)>,
+impl From<char> for Cell {
+    fn from(c: char) -> Self {
+        match c.to_digit(10) {
+            Some(d) => Cell::Height(d as u8),
+            None => Cell::Empty,
+        }
+    }
 }
 
-fn find_hiking_trails(map: &Vec<Vec<Cell>>) -> Vec<Trail> {
-    let mut trails = Vec::new();
-    let rows = map.len();
-    let cols = if rows > 0 { map[0].len() } else { 0 };
-
-    for y in 0..rows {
-        for x in 0..cols {
-            if let Cell::Height(0) = map[y][x] {
-                let mut path = Vec::new();
-                let mut visited = HashSet::new();
-                dfs(x, y, map, &mut path, &mut trails, &mut visited);
-            }
+impl Display for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Cell::Height(h) => write!(f, "{h}"),
+            Cell::Empty => write!(f, "."),
         }
     }
-
-    trails
 }
 
-fn dfs(
-    x: usize,
-    y: usize,
-    map: &Vec<Vec<Cell>>,
-    path: &mut Vec<(usize, usize)>,
-    trails: &mut Vec<Trail>,
-    visited: &mut HashSet<(usize, usize)>,
-) {
-    let rows = map.len();
-    let cols = if rows > 0 { map[0].len() } else { 0 };
-
-    let current_height = match map[y][x] {
-        Cell::Height(h) => h,
-        Cell::Empty => return,
+/// Positions one step higher than `pos`, as a trail only goes uphill.
+fn uphill(map: &Grid<Cell>, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let height = match map[pos] {
+        Cell::Height(h) => Some(h + 1),
+        Cell::Empty => None,
     };
+    map.neighbors_4(pos)
+        .filter(move |&next| matches!(map[next], Cell::Height(h) if Some(h) == height))
+}
 
-    path.push((x, y));
-    visited.insert((x, y));
-
-    if current_height == 9 {
-        trails.push(Trail { path: path.clone() });
-        path.pop();
-        visited.remove(&(x, y));
-        return;
-    }
-
-    let directions = [(-1, 0), (1, 0), (0, -1), (0, 1)];
-    for &(dx, dy) in &directions {
-        let new_x = x as isize + dx;
-        let new_y = y as isize + dy;
-
-        if new_x >= 0 && new_x < cols as isize && new_y >= 0 && new_y < rows as isize {
-            let new_x = new_x as usize;
-            let new_y = new_y as usize;
-            if !visited.contains(&(new_x, new_y)) {
-                if let Cell::Height(next_h) = map[new_y][new_x] {
-                    if next_h == current_height + 1 {
-                        dfs(new_x, new_y, map, path, trails, visited);
-                    }
-                }
-            }
-        }
-    }
-
-    path.pop();
-    visited.remove(&(x, y));
+fn trailheads(map: &Grid<Cell>) -> impl Iterator<Item = (usize, usize)> + '_ {
+    map.enumerate()
+        .filter(|(_, cell)| matches!(cell, Cell::Height(0)))
+        .map(|(pos, _)| pos)
 }
 
-#[allow(dead_code)]
-fn print_map(map: &[Vec<Cell>]) {
-    for line in map {
-        for cell in line {
-            match cell {
-                Cell::Height(h) => print!("{}", h),
-                Cell::Empty => print!("."),
-            }
-        }
-        println!();
-    }
+fn is_summit(map: &Grid<Cell>, pos: (usize, usize)) -> bool {
+    matches!(map[pos], Cell::Height(9))
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let (_, map) = parse_input(input.trim()).unwrap();
-    let trails = find_hiking_trails(&map);
-
-    let mut unique_trails = Vec::new();
-    let mut seen = HashSet::new();
-    for trail in trails {
-        let start = trail.path.first().unwrap();
-        let end = trail.path.last().unwrap();
-        if seen.insert((*start, *end)) {
-            unique_trails.push(trail);
-        }
-    }
-
-    // for trail in &unique_trails {
-    //     println!("{:?}", trail.path);
-    // }
-    // println!("Total hiking trails found: {}", unique_trails.len());
-    Some(unique_trails.len() as u32)
+    let map = Grid::parse(input, Cell::from).ok()?;
+
+    // A trailhead's score is the number of summits it can reach, the visited set is reused for every trailhead
+    let mut seen = BitGrid::for_grid(&map);
+    let score = trailheads(&map)
+        .map(|start| {
+            seen.clear();
+            dfs_with(start, |&pos| uphill(&map, pos), &mut seen)
+                .into_iter()
+                .filter(|&pos| is_summit(&map, pos))
+                .count()
+        })
+        .sum::<usize>();
+
+    Some(score as u32)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let (_, map) = parse_input(input.trim()).unwrap();
-    let trails = find_hiking_trails(&map);
-    // for trail in &trails {
-    //     println!("{:?}", trail.path);
-    // }
-    // println!("Total hiking trails found: {}", trails.len());
-    Some(trails.len() as u32)
+    let map = Grid::parse(input, Cell::from).ok()?;
+
+    // A trailhead's rating is the number of distinct trails to any summit
+    let rating: u64 = trailheads(&map)
+        .map(|start| count_paths(start, |&pos| uphill(&map, pos), |&pos| is_summit(&map, pos)))
+        .sum();
+
+    Some(rating as u32)
 }
 
 #[cfg(test)]
@@ -1,12 +1,22 @@
-advent_of_code::solution!(11);
+advent_of_code::solution!(11, part_one = [part_one_blink], generator = generate);
 
+use advent_of_code::testing::{format_numbers, numbers, Rng};
 use memoize::memoize;
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let stones: Vec<u64> = input
+fn parse_stones(input: &str) -> Vec<u64> {
+    input
         .split_whitespace()
         .map(|s| s.parse().unwrap_or(0))
-        .collect();
+        .collect()
+}
+
+/// `stones` engraved stones, see `cargo scale`.
+pub fn generate(rng: &mut Rng, stones: usize) -> String {
+    format_numbers(&numbers(rng, stones, 1_000_000), " ")
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    let stones = parse_stones(input);
 
     let total = stones
         .iter()
@@ -15,11 +25,17 @@ pub fn part_one(input: &str) -> Option<u64> {
     Some(total)
 }
 
+/// Simulates every blink instead of counting descendants, which is only feasible for part one.
+pub fn part_one_blink(input: &str) -> Option<u64> {
+    let mut stones = parse_stones(input);
+    for _ in 0..25 {
+        stones = blink(&stones);
+    }
+    Some(stones.len() as u64)
+}
+
 pub fn part_two(input: &str) -> Option<u64> {
-    let stones: Vec<u64> = input
-        .split_whitespace()
-        .map(|s| s.parse().unwrap_or(0))
-        .collect();
+    let stones = parse_stones(input);
 
     let total = stones
         .iter()
@@ -28,7 +44,6 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(total)
 }
 
-#[allow(unused)]
 fn blink(stones: &[u64]) -> Vec<u64> {
     let mut new_stones = Vec::with_capacity(stones.len() * 2);
     for &stone in stones {
@@ -36,7 +51,7 @@ fn blink(stones: &[u64]) -> Vec<u64> {
             new_stones.push(1);
         } else {
             let num_digits = count_digits(stone);
-            if num_digits % 2 == 0 {
+            if num_digits.is_multiple_of(2) {
                 let mid = num_digits / 2;
                 let divisor = 10_u64.pow(mid as u32);
                 let left = stone / divisor;
@@ -68,7 +83,7 @@ fn count_descendants(stone: u64, blinks: u32) -> u64 {
         count_descendants(1, blinks - 1)
     } else {
         let num_digits = count_digits(stone);
-        if num_digits % 2 == 0 {
+        if num_digits.is_multiple_of(2) {
             let mid = num_digits / 2;
             let divisor = 10_u64.pow(mid as u32);
             let left = stone / divisor;
@@ -83,6 +98,7 @@ fn count_descendants(stone: u64, blinks: u32) -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::testing::{cross_check, numbers, shrink_numbers};
 
     #[test]
     fn test_part_one() {
@@ -90,9 +106,51 @@ mod tests {
         assert_eq!(result, Some(55312));
     }
 
+    #[test]
+    fn test_part_one_blink() {
+        let result = part_one_blink(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(55312));
+    }
+
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(65601038650482));
     }
+
+    #[test]
+    fn cross_check_blink() {
+        // simulating blinks grows exponentially, so only the first few are compared.
+        cross_check(
+            |rng, size| {
+                (
+                    numbers(rng, size.min(5), 100_000),
+                    rng.between(0, 12) as u32,
+                )
+            },
+            |(stones, blinks)| {
+                let mut candidates: Vec<_> = shrink_numbers(stones)
+                    .into_iter()
+                    .map(|stones| (stones, *blinks))
+                    .collect();
+                if *blinks > 0 {
+                    candidates.push((stones.clone(), blinks - 1));
+                }
+                candidates
+            },
+            |(stones, blinks)| {
+                let mut stones = stones.clone();
+                for _ in 0..*blinks {
+                    stones = blink(&stones);
+                }
+                stones.len() as u64
+            },
+            |(stones, blinks)| {
+                stones
+                    .iter()
+                    .map(|&stone| count_descendants(stone, *blinks))
+                    .sum()
+            },
+        );
+    }
 }
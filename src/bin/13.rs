@@ -1,115 +1,80 @@
-advent_of_code::solution!(13);
-
-use nom::{
-    bytes::complete::tag,
-    character::complete::{self, line_ending},
-    multi::separated_list1,
-    sequence::{preceded, tuple},
-    IResult,
-};
-
-#[derive(Debug, Clone, Copy)]
-struct Point {
-    x: i64,
-    y: i64,
+advent_of_code::solution!(13, generator = generate);
+
+use advent_of_code::geometry::{Point, Vec2};
+use advent_of_code::math::solve_linear;
+use advent_of_code::parse::{ints, paragraphs};
+use advent_of_code::testing::Rng;
+
+/// `machines` claw machines, about half of which can be won, see `cargo scale`.
+pub fn generate(rng: &mut Rng, machines: usize) -> String {
+    let machines: Vec<String> = (0..machines)
+        .map(|_| {
+            let (ax, ay) = (rng.between(10, 99), rng.between(10, 99));
+            let (bx, by) = (rng.between(10, 99), rng.between(10, 99));
+            let (px, py) = if rng.chance(1, 2) {
+                let (a, b) = (rng.between(1, 100), rng.between(1, 100));
+                (a * ax + b * bx, a * ay + b * by)
+            } else {
+                (rng.between(1_000, 20_000), rng.between(1_000, 20_000))
+            };
+            format!("Button A: X+{ax}, Y+{ay}\nButton B: X+{bx}, Y+{by}\nPrize: X={px}, Y={py}\n")
+        })
+        .collect();
+    machines.join("\n")
 }
 
 #[derive(Debug, Clone, Copy)]
-struct Move {
-    x: i64,
-    y: i64,
-}
-
-#[derive(Debug)]
 struct Machine {
-    button_a: Move,
-    button_b: Move,
-    prize: Point,
-}
-
-fn parse_move(input: &str) -> IResult<&str, Move> {
-    let (input, (_, x, _, y)) = tuple((
-        tag("X"),
-        preceded(tag("+"), complete::i64),
-        tag(", Y"),
-        preceded(tag("+"), complete::i64),
-    ))(input)?;
-
-    Ok((input, Move { x, y }))
+    button_a: Vec2<i64>,
+    button_b: Vec2<i64>,
+    prize: Point<i64>,
 }
 
-fn parse_point(input: &str) -> IResult<&str, Point> {
-    let (input, (_, x, _, y)) =
-        tuple((tag("X="), complete::i64, tag(", Y="), complete::i64))(input)?;
-
-    Ok((input, Point { x, y }))
-}
+fn parse_machine(block: &str) -> Option<Machine> {
+    let [ax, ay, bx, by, px, py] = ints(block)[..] else {
+        return None;
+    };
 
-fn parse_machine(input: &str) -> IResult<&str, Machine> {
-    let (input, (_, button_a, _, _, button_b, _, _, prize, _)) = tuple((
-        tag("Button A: "),
-        parse_move,
-        line_ending,
-        tag("Button B: "),
-        parse_move,
-        line_ending,
-        tag("Prize: "),
-        parse_point,
-        line_ending,
-    ))(input)?;
-
-    Ok((
-        input,
-        Machine {
-            button_a,
-            button_b,
-            prize,
-        },
-    ))
+    Some(Machine {
+        button_a: Vec2::new(ax, ay),
+        button_b: Vec2::new(bx, by),
+        prize: Point::new(px, py),
+    })
 }
 
-fn parse_input(input: &str) -> IResult<&str, Vec<Machine>> {
-    separated_list1(line_ending, parse_machine)(input)
+fn parse_input(input: &str) -> Option<Vec<Machine>> {
+    paragraphs(input).map(parse_machine).collect()
 }
 
 fn solve_machine(machine: &Machine) -> Option<(u64, u64)> {
     // We have two equations:
     // a1 * x + b1 * y = c1
     // a2 * x + b2 * y = c2
-    let a1 = machine.button_a.x;
-    let b1 = machine.button_b.x;
-    let c1 = machine.prize.x;
-
-    let a2 = machine.button_a.y;
-    let b2 = machine.button_b.y;
-    let c2 = machine.prize.y;
-
-    // The solution is given by Cramer's rule:
-    // denominator = a1 * b2 - a2 * b1
-    // x = (c1 * b2 - c2 * b1) / denominator
-    // y = (a1 * c2 - a2 * c1) / denominator
-    let denominator = a1 * b2 - a2 * b1;
-    if denominator == 0 {
-        return None;
-    }
-
-    let x_numerator = c1 * b2 - c2 * b1;
-    let y_numerator = a1 * c2 - a2 * c1;
-    if x_numerator % denominator != 0 || y_numerator % denominator != 0 {
-        return None;
-    }
+    let Machine {
+        button_a,
+        button_b,
+        prize,
+    } = *machine;
+
+    // Solved exactly, so the part two offset cannot overflow the intermediate products.
+    // Buttons in the same direction have no unique solution, which the puzzle inputs avoid.
+    let presses = solve_linear(
+        &[vec![button_a.x, button_b.x], vec![button_a.y, button_b.y]],
+        &[prize.x, prize.y],
+    )
+    .ok()?;
 
-    let x = x_numerator / denominator;
-    let y = y_numerator / denominator;
-    if x < 0 || y < 0 {
+    let [x, y] = presses[..] else {
         return None;
-    }
+    };
+    let x = u64::try_from(x.to_integer()?).ok()?;
+    let y = u64::try_from(y.to_integer()?).ok()?;
 
-    Some((x as u64, y as u64))
+    Some((x, y))
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let (_, machines) = parse_input(input).ok()?;
+    let machines = parse_input(input)?;
     Some(
         machines
             .iter()
@@ -120,16 +85,13 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let (_, machines) = parse_input(input).ok()?;
+    let machines = parse_input(input)?;
     let updated_machines: Vec<Machine> = machines
         .iter()
         .map(|machine| Machine {
             button_a: machine.button_a,
             button_b: machine.button_b,
-            prize: Point {
-                x: machine.prize.x + 10000000000000,
-                y: machine.prize.y + 10000000000000,
-            },
+            prize: machine.prize + Vec2::new(10000000000000, 10000000000000),
         })
         .collect();
 
@@ -0,0 +1,369 @@
+/// Compact sets of grid positions, as a faster replacement for `HashSet<(usize, usize)>` in hot loops.
+///
+/// [`BitSet`] stores small integers as one bit each. [`DenseSet`] maps a key like a position, or a position with a
+/// [`Layer`] such as a [`Direction`], to such an integer, so insert and contains are a shift and a mask.
+/// [`BitGrid`] is the set of plain positions. For a dense map from positions to values, use a [`crate::grid::Grid`].
+use std::marker::PhantomData;
+
+use crate::{
+    geometry::{Compass, Direction},
+    graph::Visited,
+    grid::Grid,
+};
+
+/// A set of integers in `0..capacity`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    words: Vec<u64>,
+    capacity: usize,
+}
+
+impl BitSet {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            words: vec![0; capacity.div_ceil(64)],
+            capacity,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns whether `i` was not in the set before.
+    ///
+    /// # Panics
+    ///
+    /// If `i` is not less than the capacity, like the methods below.
+    pub fn insert(&mut self, i: usize) -> bool {
+        let (word, mask) = self.locate(i);
+        let added = *word & mask == 0;
+        *word |= mask;
+        added
+    }
+
+    /// Returns whether `i` was in the set.
+    pub fn remove(&mut self, i: usize) -> bool {
+        let (word, mask) = self.locate(i);
+        let removed = *word & mask != 0;
+        *word &= !mask;
+        removed
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        assert!(
+            i < self.capacity,
+            "{i} is outside of a set with capacity {}",
+            self.capacity
+        );
+        self.words[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    fn locate(&mut self, i: usize) -> (&mut u64, u64) {
+        assert!(
+            i < self.capacity,
+            "{i} is outside of a set with capacity {}",
+            self.capacity
+        );
+        (&mut self.words[i / 64], 1 << (i % 64))
+    }
+
+    /// Removes all elements. Writes one word per 64 elements of capacity, without reallocating.
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// The number of elements, counted with popcount.
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    /// Adds all elements of `other`, word by word.
+    ///
+    /// # Panics
+    ///
+    /// If the capacities differ, like [`Self::intersect_with`].
+    pub fn union_with(&mut self, other: &Self) {
+        self.combine(other, |a, b| a | b);
+    }
+
+    /// Keeps only the elements that are also in `other`.
+    pub fn intersect_with(&mut self, other: &Self) {
+        self.combine(other, |a, b| a & b);
+    }
+
+    fn combine(&mut self, other: &Self, op: impl Fn(u64, u64) -> u64) {
+        assert_eq!(
+            self.capacity, other.capacity,
+            "sets with different capacities"
+        );
+        for (word, &other) in self.words.iter_mut().zip(&other.words) {
+            *word = op(*word, other);
+        }
+    }
+
+    /// All elements in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(index, &word)| {
+            let mut rest = word;
+            std::iter::from_fn(move || {
+                (rest != 0).then(|| {
+                    let bit = rest.trailing_zeros() as usize;
+                    rest &= rest - 1;
+                    index * 64 + bit
+                })
+            })
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A small enum that adds a dimension to positions in a [`DenseSet`], e.g. the direction a guard faces.
+pub trait Layer: Copy {
+    /// The number of values.
+    const COUNT: usize;
+
+    /// A unique index in `0..COUNT`.
+    fn index(self) -> usize;
+
+    fn from_index(index: usize) -> Self;
+}
+
+impl Layer for Direction {
+    const COUNT: usize = 4;
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    fn from_index(index: usize) -> Self {
+        Direction::ALL[index]
+    }
+}
+
+impl Layer for Compass {
+    const COUNT: usize = 8;
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    fn from_index(index: usize) -> Self {
+        Compass::ALL[index]
+    }
+}
+
+/// A key of a [`DenseSet`]: a position, optionally with a [`Layer`].
+pub trait DenseKey: Copy {
+    /// The number of keys per position.
+    const LAYERS: usize;
+
+    /// A unique index for a grid with `width` columns.
+    fn to_index(self, width: usize) -> usize;
+
+    fn from_index(index: usize, width: usize) -> Self;
+}
+
+impl DenseKey for (usize, usize) {
+    const LAYERS: usize = 1;
+
+    fn to_index(self, width: usize) -> usize {
+        let (x, y) = self;
+        assert!(x < width, "x {x} is outside of a set with width {width}");
+        y * width + x
+    }
+
+    fn from_index(index: usize, width: usize) -> Self {
+        (index % width, index / width)
+    }
+}
+
+impl<L: Layer> DenseKey for ((usize, usize), L) {
+    const LAYERS: usize = L::COUNT;
+
+    fn to_index(self, width: usize) -> usize {
+        let (pos, layer) = self;
+        pos.to_index(width) * L::COUNT + layer.index()
+    }
+
+    fn from_index(index: usize, width: usize) -> Self {
+        (
+            <(usize, usize)>::from_index(index / L::COUNT, width),
+            L::from_index(index % L::COUNT),
+        )
+    }
+}
+
+/// A set of keys on a `width` x `height` grid, see [`DenseKey`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DenseSet<K> {
+    width: usize,
+    height: usize,
+    bits: BitSet,
+    key: PhantomData<K>,
+}
+
+/// A set of positions on a grid.
+pub type BitGrid = DenseSet<(usize, usize)>;
+
+impl<K: DenseKey> DenseSet<K> {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            bits: BitSet::new(width * height * K::LAYERS),
+            key: PhantomData,
+        }
+    }
+
+    /// An empty set with the size of `grid`.
+    pub fn for_grid<T>(grid: &Grid<T>) -> Self {
+        Self::new(grid.width(), grid.height())
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns whether `key` was not in the set before.
+    ///
+    /// # Panics
+    ///
+    /// If the position is outside of the grid, like the methods below.
+    pub fn insert(&mut self, key: K) -> bool {
+        self.bits.insert(key.to_index(self.width))
+    }
+
+    /// Returns whether `key` was in the set.
+    pub fn remove(&mut self, key: K) -> bool {
+        self.bits.remove(key.to_index(self.width))
+    }
+
+    pub fn contains(&self, key: K) -> bool {
+        self.bits.contains(key.to_index(self.width))
+    }
+
+    /// Removes all keys, see [`BitSet::clear`].
+    pub fn clear(&mut self) {
+        self.bits.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    /// Adds all keys of `other`, which has to have the same size.
+    pub fn union_with(&mut self, other: &Self) {
+        self.bits.union_with(&other.bits);
+    }
+
+    /// Keeps only the keys that are also in `other`, which has to have the same size.
+    pub fn intersect_with(&mut self, other: &Self) {
+        self.bits.intersect_with(&other.bits);
+    }
+
+    /// All keys, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = K> + '_ {
+        self.bits
+            .iter()
+            .map(|index| K::from_index(index, self.width))
+    }
+}
+
+impl<L: Layer> DenseSet<((usize, usize), L)> {
+    /// The positions with at least one key, e.g. the cells visited in any direction.
+    pub fn positions(&self) -> BitGrid {
+        let mut positions = BitGrid::new(self.width, self.height);
+        for (pos, _) in self.iter() {
+            positions.insert(pos);
+        }
+        positions
+    }
+}
+
+impl<K: DenseKey> Visited<K> for DenseSet<K> {
+    fn visit(&mut self, node: &K) -> bool {
+        self.insert(*node)
+    }
+
+    fn is_visited(&self, node: &K) -> bool {
+        self.contains(*node)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BitGrid, BitSet, DenseSet};
+    use crate::geometry::Direction;
+
+    #[test]
+    fn stores_bits() {
+        let mut set = BitSet::new(130);
+        assert!(set.is_empty());
+        assert!(set.insert(3));
+        assert!(!set.insert(3));
+        assert!(set.insert(64));
+        assert!(set.insert(129));
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [3, 64, 129]);
+
+        let mut other = BitSet::new(130);
+        other.insert(64);
+        other.insert(100);
+        let mut union = set.clone();
+        union.union_with(&other);
+        assert_eq!(union.iter().collect::<Vec<_>>(), [3, 64, 100, 129]);
+        set.intersect_with(&other);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [64]);
+
+        assert!(set.remove(64));
+        assert!(!set.remove(64));
+        union.clear();
+        assert!(union.is_empty());
+        assert_eq!(union.capacity(), 130);
+    }
+
+    #[test]
+    fn stores_positions() {
+        let mut visited = BitGrid::new(3, 2);
+        assert!(visited.insert((2, 1)));
+        assert!(visited.insert((0, 1)));
+        assert!(visited.contains((2, 1)));
+        assert!(!visited.contains((1, 1)));
+        assert_eq!(visited.iter().collect::<Vec<_>>(), [(0, 1), (2, 1)]);
+
+        let mut states = DenseSet::new(3, 2);
+        assert!(states.insert(((1, 1), Direction::Left)));
+        assert!(states.insert(((1, 1), Direction::Up)));
+        assert!(!states.insert(((1, 1), Direction::Up)));
+        assert!(!states.contains(((1, 1), Direction::Down)));
+        assert_eq!(states.len(), 2);
+        assert_eq!(
+            states.iter().collect::<Vec<_>>(),
+            [((1, 1), Direction::Up), ((1, 1), Direction::Left)]
+        );
+        assert_eq!(states.positions().iter().collect::<Vec<_>>(), [(1, 1)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "x 3 is outside of a set with width 3")]
+    fn checks_bounds() {
+        BitGrid::new(3, 2).insert((3, 0));
+    }
+}
@@ -0,0 +1,247 @@
+/// Disjoint sets and connected components of a grid, e.g. the regions of a garden.
+///
+/// [`UnionFind`] merges sets of indices in nearly constant time. [`label_components`] uses it to give every cell of
+/// a [`Grid`] the label of its component and collects [`Component`] statistics like size and perimeter.
+use crate::grid::{Grid, NEIGHBORS_4};
+
+/// Disjoint sets of the indices `0..len`, with path compression and union by rank.
+#[derive(Clone, Debug)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+    sizes: Vec<usize>,
+    sets: usize,
+}
+
+impl UnionFind {
+    /// `len` sets with one index each.
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            ranks: vec![0; len],
+            sizes: vec![1; len],
+            sets: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// The representative of the set that contains `i`.
+    pub fn find(&mut self, mut i: usize) -> usize {
+        // path halving: every visited index skips its parent, which flattens the tree as it goes.
+        while self.parents[i] != i {
+            self.parents[i] = self.parents[self.parents[i]];
+            i = self.parents[i];
+        }
+        i
+    }
+
+    /// Merges the sets of `a` and `b`, returns whether they were different sets.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        if self.ranks[a] < self.ranks[b] {
+            (a, b) = (b, a);
+        }
+        if self.ranks[a] == self.ranks[b] {
+            self.ranks[a] += 1;
+        }
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
+        self.sets -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The number of indices in the set that contains `i`.
+    pub fn size(&mut self, i: usize) -> usize {
+        let root = self.find(i);
+        self.sizes[root]
+    }
+
+    /// The number of disjoint sets.
+    pub fn set_count(&self) -> usize {
+        self.sets
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A connected component of a grid, see [`label_components`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Component {
+    /// The index of the component in [`Components::components`] and its label in [`Components::labels`].
+    pub label: usize,
+    /// The first cell of the component, row by row.
+    pub start: (usize, usize),
+    /// The number of cells.
+    pub size: usize,
+    /// The top-left corner of the bounding box.
+    pub min: (usize, usize),
+    /// The bottom-right corner of the bounding box, inclusive.
+    pub max: (usize, usize),
+    /// The number of cell edges between the component and other components or the outside of the grid.
+    pub perimeter: usize,
+    /// The cells with at least one orthogonal neighbor outside the component, row by row.
+    pub boundary: Vec<(usize, usize)>,
+}
+
+/// The connected components of a grid.
+#[derive(Clone, Debug)]
+pub struct Components {
+    /// The label of every cell.
+    pub labels: Grid<usize>,
+    /// All components, ordered by their first cell row by row.
+    pub components: Vec<Component>,
+}
+
+impl Components {
+    /// The component that contains `pos`.
+    pub fn at(&self, pos: (usize, usize)) -> &Component {
+        &self.components[self.labels[pos]]
+    }
+
+    /// The cells of every component, indexed by label. Each list is ordered row by row.
+    pub fn cells(&self) -> Vec<Vec<(usize, usize)>> {
+        let mut cells: Vec<Vec<_>> = self
+            .components
+            .iter()
+            .map(|component| Vec::with_capacity(component.size))
+            .collect();
+        for (pos, &label) in self.labels.enumerate() {
+            cells[label].push(pos);
+        }
+        cells
+    }
+}
+
+/// Splits a grid into components of orthogonally adjacent cells for which `connected` holds, e.g.
+/// `label_components(&garden, |a, b| a == b)` for regions of the same plant. `connected` has to be an equivalence.
+pub fn label_components<T>(
+    grid: &Grid<T>,
+    mut connected: impl FnMut(&T, &T) -> bool,
+) -> Components {
+    let width = grid.width();
+    let index = |(x, y): (usize, usize)| y * width + x;
+
+    // joining every cell with its right and lower neighbor is enough to cover all edges.
+    let mut sets = UnionFind::new(width * grid.height());
+    for (pos, cell) in grid.enumerate() {
+        for next in [grid.offset(pos, (1, 0)), grid.offset(pos, (0, 1))]
+            .into_iter()
+            .flatten()
+        {
+            if connected(cell, &grid[next]) {
+                sets.union(index(pos), index(next));
+            }
+        }
+    }
+
+    // labels are assigned in the order the components are first seen.
+    let mut root_labels = vec![usize::MAX; sets.len()];
+    let mut components: Vec<Component> = vec![];
+    let mut labels = Grid::new(width, grid.height(), 0);
+    for pos in grid.positions() {
+        let root = sets.find(index(pos));
+        if root_labels[root] == usize::MAX {
+            root_labels[root] = components.len();
+            components.push(Component {
+                label: components.len(),
+                start: pos,
+                size: 0,
+                min: pos,
+                max: pos,
+                perimeter: 0,
+                boundary: vec![],
+            });
+        }
+        labels[pos] = root_labels[root];
+    }
+
+    for (pos, &label) in labels.enumerate() {
+        let outside = NEIGHBORS_4
+            .into_iter()
+            .filter(|&delta| {
+                grid.offset(pos, delta)
+                    .is_none_or(|next| labels[next] != label)
+            })
+            .count();
+
+        let component = &mut components[label];
+        component.size += 1;
+        component.min = (component.min.0.min(pos.0), component.min.1.min(pos.1));
+        component.max = (component.max.0.max(pos.0), component.max.1.max(pos.1));
+        component.perimeter += outside;
+        if outside > 0 {
+            component.boundary.push(pos);
+        }
+    }
+
+    Components { labels, components }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{label_components, UnionFind};
+    use crate::grid::Grid;
+
+    #[test]
+    fn merges_sets() {
+        let mut sets = UnionFind::new(6);
+        assert_eq!(sets.set_count(), 6);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 2));
+        assert!(sets.connected(0, 3));
+        assert!(!sets.connected(0, 4));
+        assert_eq!(sets.size(2), 4);
+        assert_eq!(sets.size(5), 1);
+        assert_eq!(sets.set_count(), 3);
+        assert_eq!(sets.len(), 6);
+    }
+
+    #[test]
+    fn labels_components() {
+        let garden = Grid::parse("AAAA\nBBCD\nBBCC\nEEEC", |c| c).unwrap();
+        let regions = label_components(&garden, |a, b| a == b);
+        assert_eq!(regions.components.len(), 5);
+        assert_eq!(regions.labels.to_string(), "0000\n1123\n1122\n4442\n");
+
+        let c = regions.at((2, 1));
+        assert_eq!((c.label, c.start, c.size, c.perimeter), (2, (2, 1), 4, 10));
+        assert_eq!((c.min, c.max), ((2, 1), (3, 3)));
+        assert_eq!(c.boundary.len(), 4);
+        assert_eq!(regions.cells()[2], [(2, 1), (2, 2), (3, 2), (3, 3)]);
+
+        let a = &regions.components[0];
+        assert_eq!((a.size, a.perimeter, a.max), (4, 10, (3, 0)));
+    }
+
+    #[test]
+    fn labels_enclosed_components() {
+        let garden = Grid::parse("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO", |c| c).unwrap();
+        let regions = label_components(&garden, |a, b| a == b);
+        assert_eq!(regions.components.len(), 5);
+
+        let outer = &regions.components[0];
+        assert_eq!((outer.size, outer.perimeter), (21, 36));
+        assert_eq!(outer.boundary.len(), 20);
+        assert!(!outer.boundary.contains(&(2, 2)));
+        assert!(regions.components[1..]
+            .iter()
+            .all(|inner| inner.size == 1 && inner.perimeter == 4));
+    }
+}
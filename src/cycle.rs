@@ -0,0 +1,223 @@
+/// Cycle detection for simulations that repeat a step until they stop or end up in a loop.
+///
+/// A simulation is an initial state and a step function that returns the next state, or `None` once the simulation
+/// stops, e.g. when a guard leaves the map. Steps have to be deterministic, so a repeated state means a loop.
+use std::{collections::HashMap, hash::Hash};
+
+/// A loop in a sequence of states: the state at index `start` is the first one that repeats, every `length` steps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Loop {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Loop {
+    /// The index of the first state that equals the state after `n` steps.
+    pub fn index_after(&self, n: u64) -> usize {
+        let start = self.start as u64;
+        if n < start {
+            n as usize
+        } else {
+            self.start + ((n - start) % self.length as u64) as usize
+        }
+    }
+}
+
+/// Brent's algorithm: finds a loop in O(start + length) steps with constant memory.
+/// `None` if the simulation stops. Runs forever if it neither stops nor repeats.
+pub fn brent<S: Clone + PartialEq>(
+    initial: &S,
+    mut step: impl FnMut(&S) -> Option<S>,
+) -> Option<Loop> {
+    // find the length by moving the tortoise to the hare at every power of two.
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(initial)?;
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare)?;
+        length += 1;
+    }
+
+    // with the hare `length` steps ahead, both meet at the start of the loop.
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    for _ in 0..length {
+        hare = step(&hare)?;
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        start += 1;
+    }
+
+    Some(Loop { start, length })
+}
+
+/// Floyd's algorithm: the tortoise takes one step, the hare two. Usually slower than [`brent`].
+/// `None` if the simulation stops. Runs forever if it neither stops nor repeats.
+pub fn floyd<S: Clone + PartialEq>(
+    initial: &S,
+    mut step: impl FnMut(&S) -> Option<S>,
+) -> Option<Loop> {
+    let mut tortoise = step(initial)?;
+    let mut hare = step(&tortoise)?;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        let next = step(&hare)?;
+        hare = step(&next)?;
+    }
+
+    // the meeting point is a multiple of the loop length away from the start.
+    let mut tortoise = initial.clone();
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        start += 1;
+    }
+
+    let mut length = 1;
+    let mut hare = step(&tortoise)?;
+    while tortoise != hare {
+        hare = step(&hare)?;
+        length += 1;
+    }
+
+    Some(Loop { start, length })
+}
+
+/// The state after `n` steps, where `n` may be huge as loops are skipped. `None` if the simulation stops earlier.
+/// Finds the loop first, so it takes at least O(start + length) steps even for a small `n`.
+pub fn state_after<S: Clone + PartialEq>(
+    initial: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+    n: u64,
+) -> Option<S> {
+    let n = match brent(&initial, &mut step) {
+        Some(found) => found.index_after(n),
+        // the simulation stops, so stepping directly ends early as well.
+        None => usize::try_from(n).unwrap_or(usize::MAX),
+    };
+    std::iter::successors(Some(initial), |state| step(state)).nth(n)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Every state of a simulation until it stops or a state repeats, see [`trace`].
+#[derive(Clone, Debug)]
+pub struct Trace<S> {
+    /// All states in order, without the repeated one.
+    pub states: Vec<S>,
+    /// The index of every state in `states`.
+    pub seen: HashMap<S, usize>,
+    /// The loop, if a state repeated.
+    pub cycle: Option<Loop>,
+}
+
+impl<S> Trace<S> {
+    /// The state after `n` steps, `None` if the simulation stopped earlier.
+    pub fn state_after(&self, n: u64) -> Option<&S> {
+        match self.cycle {
+            Some(found) => self.states.get(found.index_after(n)),
+            None => self.states.get(usize::try_from(n).ok()?),
+        }
+    }
+}
+
+/// Runs a simulation and remembers every state, which takes more memory than [`brent`] but fewer steps and keeps the
+/// states around, e.g. to count distinct positions on the way.
+pub fn trace<S: Clone + Eq + Hash>(initial: S, mut step: impl FnMut(&S) -> Option<S>) -> Trace<S> {
+    let mut states = vec![];
+    let mut seen = HashMap::new();
+    let mut state = Some(initial);
+
+    while let Some(current) = state {
+        if let Some(&start) = seen.get(&current) {
+            let cycle = Loop {
+                start,
+                length: states.len() - start,
+            };
+            return Trace {
+                states,
+                seen,
+                cycle: Some(cycle),
+            };
+        }
+
+        state = step(&current);
+        seen.insert(current.clone(), states.len());
+        states.push(current);
+    }
+
+    Trace {
+        states,
+        seen,
+        cycle: None,
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{brent, floyd, state_after, trace, Loop};
+
+    /// 0, 1, ..., 10, then back to 3.
+    fn rho(x: &u32) -> Option<u32> {
+        Some(if *x < 10 { x + 1 } else { 3 })
+    }
+
+    fn stops(x: &u32) -> Option<u32> {
+        (*x < 5).then_some(x + 1)
+    }
+
+    #[test]
+    fn finds_loops() {
+        let expected = Loop {
+            start: 3,
+            length: 8,
+        };
+        assert_eq!(brent(&0, rho), Some(expected));
+        assert_eq!(floyd(&0, rho), Some(expected));
+        assert_eq!(trace(0, rho).cycle, Some(expected));
+
+        let ring = Loop {
+            start: 0,
+            length: 4,
+        };
+        assert_eq!(brent(&0, |x| Some((x + 1) % 4)), Some(ring));
+        assert_eq!(floyd(&0, |x| Some((x + 1) % 4)), Some(ring));
+        assert_eq!(
+            brent(&7, |x| Some(*x)),
+            Some(Loop {
+                start: 0,
+                length: 1
+            })
+        );
+
+        assert_eq!(brent(&0, stops), None);
+        assert_eq!(floyd(&0, stops), None);
+        assert_eq!(trace(0, stops).cycle, None);
+    }
+
+    #[test]
+    fn skips_loops() {
+        assert_eq!(state_after(0, rho, 2), Some(2));
+        assert_eq!(state_after(0, rho, 11), Some(3));
+        // 10^12 - 3 steps into the loop of length 8 is 5 steps past its start.
+        assert_eq!(state_after(0, rho, 1_000_000_000_000), Some(8));
+        assert_eq!(state_after(0, stops, 5), Some(5));
+        assert_eq!(state_after(0, stops, 6), None);
+
+        let rho_trace = trace(0, rho);
+        assert_eq!(rho_trace.states.len(), 11);
+        assert_eq!(rho_trace.seen[&7], 7);
+        assert_eq!(rho_trace.state_after(1_000_000_000_000), Some(&8));
+        assert_eq!(trace(0, stops).state_after(6), None);
+    }
+}
@@ -0,0 +1,315 @@
+/// Points, vectors and directions on a 2d grid.
+///
+/// Like [`crate::grid`], `x` grows to the right and `y` grows downwards, so [`Direction::Up`] is `(0, -1)`.
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A difference between two points, e.g. a step in some direction.
+pub type Vec2<T> = Point<T>;
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>> Point<T> {
+    /// Number of orthogonal steps between two points.
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// Number of steps between two points when diagonal steps are allowed.
+    pub fn chebyshev(self, other: Self) -> T {
+        let (dx, dy) = (abs_diff(self.x, other.x), abs_diff(self.y, other.y));
+        if dx > dy {
+            dx
+        } else {
+            dy
+        }
+    }
+}
+
+impl<T: Copy + Mul<Output = T> + Sub<Output = T>> Point<T> {
+    /// The z component of the cross product, i.e. the determinant of the two vectors.
+    /// Zero if they are parallel, positive if `other` is clockwise of `self` (since `y` grows downwards).
+    pub fn cross(self, other: Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+/// Works for unsigned types as well, unlike `abs()`.
+fn abs_diff<T: PartialOrd + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+/// Scales a vector, e.g. `direction.delta() * steps`.
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Self::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// One of the 4 orthogonal directions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions, clockwise starting with `Up`.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// Turns 90° clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    /// Turns 90° counter-clockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// A single step in this direction.
+    pub fn delta(self) -> Vec2<isize> {
+        match self {
+            Direction::Up => Vec2::new(0, -1),
+            Direction::Right => Vec2::new(1, 0),
+            Direction::Down => Vec2::new(0, 1),
+            Direction::Left => Vec2::new(-1, 0),
+        }
+    }
+
+    /// Parses arrows (`^>v<`) as used in many puzzle inputs.
+    pub fn from_arrow(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Direction::Up),
+            '>' => Some(Direction::Right),
+            'v' => Some(Direction::Down),
+            '<' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// One of the 8 compass directions, for puzzles with diagonal moves. North is up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Compass {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Compass {
+    /// All directions, clockwise starting with `N`.
+    pub const ALL: [Compass; 8] = [
+        Compass::N,
+        Compass::NE,
+        Compass::E,
+        Compass::SE,
+        Compass::S,
+        Compass::SW,
+        Compass::W,
+        Compass::NW,
+    ];
+
+    /// The diagonal directions, clockwise starting with `NE`.
+    pub const DIAGONALS: [Compass; 4] = [Compass::NE, Compass::SE, Compass::SW, Compass::NW];
+
+    /// Turns 45° clockwise.
+    pub fn rotate_cw(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Turns 45° counter-clockwise.
+    pub fn rotate_ccw(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// A single step in this direction, diagonal steps move along both axes.
+    pub fn delta(self) -> Vec2<isize> {
+        let (dx, dy) = match self {
+            Compass::N => (0, -1),
+            Compass::NE => (1, -1),
+            Compass::E => (1, 0),
+            Compass::SE => (1, 1),
+            Compass::S => (0, 1),
+            Compass::SW => (-1, 1),
+            Compass::W => (-1, 0),
+            Compass::NW => (-1, -1),
+        };
+        Vec2::new(dx, dy)
+    }
+}
+
+impl From<Direction> for Compass {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Up => Compass::N,
+            Direction::Right => Compass::E,
+            Direction::Down => Compass::S,
+            Direction::Left => Compass::W,
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Compass, Direction, Point, Vec2};
+
+    #[test]
+    fn does_arithmetic() {
+        let mut p = Point::new(3, 4);
+        assert_eq!(p + Vec2::new(1, -1), Point::new(4, 3));
+        assert_eq!(p - Point::new(5, 5), Point::new(-2, -1));
+        assert_eq!(-p, Point::new(-3, -4));
+        assert_eq!(Direction::Left.delta() * 3, Vec2::new(-3, 0));
+
+        p += Vec2::new(1, 1);
+        p -= Vec2::new(0, 2);
+        assert_eq!(p, Point::new(4, 3));
+        assert_eq!(<(i32, i32)>::from(p), (4, 3));
+        assert_eq!(p.to_string(), "(4, 3)");
+    }
+
+    #[test]
+    fn measures_distances() {
+        let (a, b) = (Point::new(1_u32, 8), Point::new(4, 2));
+        assert_eq!(a.manhattan(b), 9);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!(b.manhattan(a), 9);
+
+        assert_eq!(Vec2::new(94, 34).cross(Vec2::new(22, 67)), 5550);
+        assert_eq!(Vec2::new(2, 4).cross(Vec2::new(1, 2)), 0);
+    }
+
+    #[test]
+    fn turns_directions() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Down.opposite(), Direction::Up);
+        for direction in Direction::ALL {
+            assert_eq!(direction.opposite().delta(), -direction.delta());
+            assert_eq!(Direction::from_arrow(direction.arrow()), Some(direction));
+        }
+    }
+
+    #[test]
+    fn rotates_compass_directions() {
+        assert_eq!(Compass::NW.rotate_cw(), Compass::N);
+        assert_eq!(Compass::N.rotate_ccw(), Compass::NW);
+        assert_eq!(Compass::SE.opposite(), Compass::NW);
+        assert!(Compass::SW.is_diagonal());
+        assert!(!Compass::from(Direction::Right).is_diagonal());
+        assert_eq!(Compass::NE.delta(), Compass::N.delta() + Compass::E.delta());
+    }
+}
@@ -0,0 +1,507 @@
+/// Graph searches over implicit graphs.
+///
+/// Instead of an explicit graph type, every search takes a closure that returns the neighbors of a node.
+/// This fits grids (`|&pos| grid.neighbors_4(pos)`) as well as adjacency maps (`|node| edges[node].clone()`).
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    error::Error,
+    fmt::{Debug, Display},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Distances from a start node and the parent of every reached node, as found by [`bfs`] or [`dijkstra`].
+#[derive(Clone, Debug)]
+pub struct SearchTree<N, C> {
+    pub distances: HashMap<N, C>,
+    pub parents: HashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> SearchTree<N, C> {
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    /// The path from the start to `node` (both inclusive), `None` if `node` was not reached.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.distances.get(node)?;
+
+        let mut path = vec![node.clone()];
+        while let Some(parent) = self.parents.get(path.last()?) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// A set of visited nodes for the searches that accept one.
+pub trait Visited<N> {
+    /// Marks `node` as visited, returns whether it was not visited before.
+    fn visit(&mut self, node: &N) -> bool;
+
+    fn is_visited(&self, node: &N) -> bool;
+}
+
+impl<N: Clone + Eq + Hash> Visited<N> for HashSet<N> {
+    fn visit(&mut self, node: &N) -> bool {
+        !self.contains(node) && self.insert(node.clone())
+    }
+
+    fn is_visited(&self, node: &N) -> bool {
+        self.contains(node)
+    }
+}
+
+/// Breadth-first search from `start`. Distances count edges.
+pub fn bfs<N, I>(start: N, mut neighbors: impl FnMut(&N) -> I) -> SearchTree<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut parents = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[&node];
+        for next in neighbors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    SearchTree { distances, parents }
+}
+
+/// Depth-first search from `start`. Returns the reachable nodes in the order they were visited.
+pub fn dfs<N, I>(start: N, neighbors: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    dfs_with(start, neighbors, &mut HashSet::new())
+}
+
+/// Like [`dfs`], but records visited nodes in `seen`, e.g. a [`crate::bitset::DenseSet`] that is cleared and reused
+/// for several searches. Nodes already in `seen` are skipped.
+pub fn dfs_with<N, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    seen: &mut impl Visited<N>,
+) -> Vec<N>
+where
+    N: Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut order = vec![];
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        if !seen.visit(&node) {
+            continue;
+        }
+        let mut next: Vec<N> = neighbors(&node)
+            .into_iter()
+            .filter(|next| !seen.is_visited(next))
+            .collect();
+        // reversed, so the first neighbor is visited first like in a recursive search.
+        next.reverse();
+        stack.extend(next);
+        order.push(node);
+    }
+
+    order
+}
+
+/// All nodes connected to `start`, including itself. Restrict `neighbors` to fill a region, e.g. cells of one kind.
+pub fn flood_fill<N, I>(start: N, mut neighbors: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::from([start.clone()]);
+    let mut region = vec![];
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        for next in neighbors(&node) {
+            if seen.insert(next.clone()) {
+                stack.push(next);
+            }
+        }
+        region.push(node);
+    }
+
+    region
+}
+
+/// An entry of the priority queue, ordered by cost only so nodes don't need to implement `Ord`.
+struct Queued<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    // reversed, as `BinaryHeap` is a max-heap.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// Dijkstra's algorithm from `start` over edges with non-negative costs. `neighbors` returns `(node, cost)` pairs.
+pub fn dijkstra<N, C, I>(start: N, mut neighbors: impl FnMut(&N) -> I) -> SearchTree<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut distances = HashMap::from([(start.clone(), C::default())]);
+    let mut parents = HashMap::new();
+    let mut heap = BinaryHeap::from([Queued {
+        priority: C::default(),
+        cost: C::default(),
+        node: start,
+    }]);
+
+    while let Some(Queued { cost, node, .. }) = heap.pop() {
+        if distances.get(&node).is_some_and(|&best| cost > best) {
+            continue;
+        }
+        for (next, step) in neighbors(&node) {
+            let next_cost = cost + step;
+            if distances.get(&next).is_none_or(|&best| next_cost < best) {
+                distances.insert(next.clone(), next_cost);
+                parents.insert(next.clone(), node.clone());
+                heap.push(Queued {
+                    priority: next_cost,
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+    }
+
+    SearchTree { distances, parents }
+}
+
+/// A* search from `start` to the first node that satisfies `is_goal`. Returns the cost and path (both ends inclusive).
+/// `heuristic` must never overestimate the remaining cost, e.g. the Manhattan distance on a grid.
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(C, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut tree = SearchTree {
+        distances: HashMap::from([(start.clone(), C::default())]),
+        parents: HashMap::new(),
+    };
+    let mut heap = BinaryHeap::from([Queued {
+        priority: heuristic(&start),
+        cost: C::default(),
+        node: start,
+    }]);
+
+    while let Some(Queued { cost, node, .. }) = heap.pop() {
+        if is_goal(&node) {
+            return Some((cost, tree.path_to(&node)?));
+        }
+        if tree.distances.get(&node).is_some_and(|&best| cost > best) {
+            continue;
+        }
+        for (next, step) in neighbors(&node) {
+            let next_cost = cost + step;
+            if tree
+                .distances
+                .get(&next)
+                .is_none_or(|&best| next_cost < best)
+            {
+                tree.distances.insert(next.clone(), next_cost);
+                tree.parents.insert(next.clone(), node.clone());
+                heap.push(Queued {
+                    priority: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Number of distinct paths from `start` to nodes that satisfy `is_goal`. Paths end at the first goal.
+/// The graph has to be acyclic, counts are memoized per node.
+pub fn count_paths<N, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> u64
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    fn count<N: Clone + Eq + Hash, I: IntoIterator<Item = N>>(
+        node: &N,
+        neighbors: &mut impl FnMut(&N) -> I,
+        is_goal: &mut impl FnMut(&N) -> bool,
+        memo: &mut HashMap<N, u64>,
+    ) -> u64 {
+        if is_goal(node) {
+            return 1;
+        }
+        if let Some(&paths) = memo.get(node) {
+            return paths;
+        }
+        let next: Vec<N> = neighbors(node).into_iter().collect();
+        let paths = next
+            .iter()
+            .map(|next| count(next, neighbors, is_goal, memo))
+            .sum();
+        memo.insert(node.clone(), paths);
+        paths
+    }
+
+    count(&start, &mut neighbors, &mut is_goal, &mut HashMap::new())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Orders `nodes` so that every node comes before its successors (Kahn's algorithm).
+/// Whenever several nodes could come next, the one listed first in `nodes` is taken, so nodes without ordering
+/// constraints keep their relative order. Successors that are not in `nodes` are ignored.
+pub fn topological_sort<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+) -> Result<Vec<N>, Cycle<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let nodes: Vec<N> = nodes.into_iter().collect();
+    let index: HashMap<&N, usize> = nodes.iter().enumerate().map(|(i, n)| (n, i)).collect();
+
+    let mut edges = vec![vec![]; nodes.len()];
+    let mut reversed = vec![vec![]; nodes.len()];
+    let mut in_degree = vec![0; nodes.len()];
+
+    for (i, node) in nodes.iter().enumerate() {
+        for next in successors(node) {
+            if let Some(&j) = index.get(&next) {
+                edges[i].push(j);
+                reversed[j].push(i);
+                in_degree[j] += 1;
+            }
+        }
+    }
+
+    // a min-heap on the position in `nodes`, which keeps the order stable.
+    let mut ready: BinaryHeap<Reverse<usize>> = (0..nodes.len())
+        .filter(|&i| in_degree[i] == 0)
+        .map(Reverse)
+        .collect();
+    let mut order = vec![];
+
+    while let Some(Reverse(i)) = ready.pop() {
+        order.push(i);
+        for &j in &edges[i] {
+            in_degree[j] -= 1;
+            if in_degree[j] == 0 {
+                ready.push(Reverse(j));
+            }
+        }
+    }
+
+    if order.len() == nodes.len() {
+        return Ok(order.into_iter().map(|i| nodes[i].clone()).collect());
+    }
+
+    // every node left over has a left-over predecessor, so walking backwards has to run into a cycle.
+    let mut walk = vec![(0..nodes.len())
+        .find(|&i| in_degree[i] > 0)
+        .unwrap_or_default()];
+    let start = loop {
+        let current = walk[walk.len() - 1];
+        let Some(&previous) = reversed[current].iter().find(|&&p| in_degree[p] > 0) else {
+            break walk.len() - 1;
+        };
+        if let Some(position) = walk.iter().position(|&i| i == previous) {
+            break position;
+        }
+        walk.push(previous);
+    };
+
+    let mut cycle: Vec<N> = walk[start..].iter().map(|&i| nodes[i].clone()).collect();
+    cycle.reverse();
+    Err(Cycle { nodes: cycle })
+}
+
+/// An error which is returned by [`topological_sort`] if the nodes can not be ordered.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle<N> {
+    /// The nodes of one cycle, each one a predecessor of the next and the last one of the first.
+    pub nodes: Vec<N>,
+}
+
+impl<N: Debug> Error for Cycle<N> {}
+
+impl<N: Debug> Display for Cycle<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the graph has a cycle: ")?;
+        for node in &self.nodes {
+            write!(f, "{node:?} -> ")?;
+        }
+        match self.nodes.first() {
+            Some(first) => write!(f, "{first:?}"),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        astar, bfs, count_paths, dfs, dfs_with, dijkstra, flood_fill, topological_sort, Cycle,
+    };
+    use crate::grid::Grid;
+    use std::collections::{HashMap, HashSet};
+
+    fn maze() -> Grid<char> {
+        Grid::parse("S.#.\n..#.\n...E", |c| c).unwrap()
+    }
+
+    fn open(grid: &Grid<char>, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        grid.neighbors_4(pos).filter(|&next| grid[next] != '#')
+    }
+
+    #[test]
+    fn searches_breadth_first() {
+        let grid = maze();
+        let tree = bfs((0, 0), |&pos| open(&grid, pos));
+        assert_eq!(tree.distance(&(3, 2)), Some(5));
+        assert_eq!(tree.distance(&(2, 0)), None);
+
+        let path = tree.path_to(&(3, 2)).unwrap();
+        assert_eq!(path.len(), 6);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(tree.path_to(&(0, 0)), Some(vec![(0, 0)]));
+    }
+
+    #[test]
+    fn searches_depth_first() {
+        let edges = HashMap::from([(1, vec![2, 3]), (2, vec![4]), (3, vec![4]), (4, vec![])]);
+        assert_eq!(dfs(1, |n| edges[n].clone()), [1, 2, 4, 3]);
+
+        // nodes seen by an earlier search are skipped.
+        let mut seen = HashSet::from([2]);
+        assert_eq!(dfs_with(1, |n| edges[n].clone(), &mut seen), [1, 3, 4]);
+        assert_eq!(seen.len(), 4);
+
+        let grid = maze();
+        let mut region = flood_fill((0, 0), |&pos| open(&grid, pos));
+        region.sort_unstable();
+        assert_eq!(region.len(), 10);
+        assert!(!region.contains(&(2, 0)));
+    }
+
+    #[test]
+    fn finds_cheapest_paths() {
+        // going through `9` is shorter, but more expensive.
+        let grid = Grid::parse("1911\n1111", |c| c.to_digit(10).unwrap()).unwrap();
+        let neighbors =
+            |&pos: &(usize, usize)| grid.neighbors_4(pos).map(|next| (next, grid[next]));
+
+        let tree = dijkstra((0, 0), neighbors);
+        assert_eq!(tree.distance(&(3, 0)), Some(5));
+        assert_eq!(tree.path_to(&(3, 0)).unwrap()[1], (0, 1));
+
+        let (cost, path) = astar(
+            (0, 0),
+            neighbors,
+            |&(x, y)| (3 - x + y) as u32,
+            |&pos| pos == (3, 0),
+        )
+        .unwrap();
+        assert_eq!(cost, 5);
+        assert_eq!(path.len(), 6);
+        assert_eq!(astar((0, 0), neighbors, |_| 0, |_| false), None);
+    }
+
+    #[test]
+    fn counts_paths() {
+        // a grid where every step goes right or down.
+        let grid = Grid::new(3, 3, ());
+        let paths = count_paths(
+            (0, 0),
+            |&pos| {
+                grid.offset(pos, (1, 0))
+                    .into_iter()
+                    .chain(grid.offset(pos, (0, 1)))
+            },
+            |&pos| pos == (2, 2),
+        );
+        assert_eq!(paths, 6);
+    }
+
+    #[test]
+    fn sorts_topologically() {
+        let before = [(47, 53), (97, 13), (97, 61), (75, 47), (61, 53), (97, 47)];
+        let successors = |n: &u32| {
+            before
+                .iter()
+                .filter(|(a, _)| a == n)
+                .map(|(_, b)| *b)
+                .collect::<Vec<_>>()
+        };
+
+        let sorted = topological_sort([53, 61, 47, 97, 75], successors).unwrap();
+        assert_eq!(sorted, [97, 61, 75, 47, 53]);
+
+        // `a` waits for `c`, but then comes before `d` again, which was listed after it.
+        let sorted =
+            topological_sort(['a', 'b', 'c', 'd'], |&n| (n == 'c').then_some('a')).unwrap();
+        assert_eq!(sorted, ['b', 'c', 'a', 'd']);
+
+        let cyclic = [(1, 2), (2, 3), (3, 1), (1, 4)];
+        let result = topological_sort([4, 3, 2, 1], |n: &u32| {
+            cyclic
+                .iter()
+                .filter(|(a, _)| a == n)
+                .map(|(_, b)| *b)
+                .collect::<Vec<_>>()
+        });
+        let Err(Cycle { nodes }) = result else {
+            panic!("expected a cycle");
+        };
+        assert_eq!(nodes.len(), 3);
+        assert!(nodes.iter().all(|n| [1, 2, 3].contains(n)));
+        assert!(Cycle { nodes: vec![1, 2] }
+            .to_string()
+            .ends_with("1 -> 2 -> 1"));
+    }
+}
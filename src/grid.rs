@@ -0,0 +1,350 @@
+/// A rectangular grid of cells, as in most puzzles with a map.
+///
+/// Cells are stored row by row in a single `Vec`. Positions are `(x, y)` with `x` as the column and `y` as the row,
+/// `(0, 0)` is the top-left cell. Methods that may leave the grid take signed coordinates and return `None` outside.
+use std::{
+    error::Error,
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+/// Offsets of the 4 orthogonal neighbors: right, down, left, up.
+pub const NEIGHBORS_4: [(isize, isize); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+/// Offsets of all 8 neighbors, clockwise starting to the right.
+pub const NEIGHBORS_8: [(isize, isize); 8] = [
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A `width` x `height` grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Parses one row per line, mapping every char to a cell.
+    /// Blank lines and whitespace around lines are skipped, so indented test inputs work as well.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> T) -> Result<Self, GridError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for (index, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let len = cells.len();
+            cells.extend(line.chars().map(&mut cell));
+            let row_width = cells.len() - len;
+
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(GridError::Ragged {
+                        line: index + 1,
+                        expected: width,
+                        found: row_width,
+                    });
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        let width = width.ok_or(GridError::Empty)?;
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, x: isize, y: isize) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    /// The position `(dx, dy)` away from `(x, y)`, if it is part of the grid.
+    pub fn offset(
+        &self,
+        (x, y): (usize, usize),
+        (dx, dy): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let (x, y) = (x as isize + dx, y as isize + dy);
+        self.contains(x, y).then_some((x as usize, y as usize))
+    }
+
+    pub fn get(&self, x: isize, y: isize) -> Option<&T> {
+        self.contains(x, y)
+            .then(|| &self.cells[y as usize * self.width + x as usize])
+    }
+
+    pub fn get_mut(&mut self, x: isize, y: isize) -> Option<&mut T> {
+        self.contains(x, y)
+            .then(|| &mut self.cells[y as usize * self.width + x as usize])
+    }
+
+    /// All cells, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i % width, i / width))
+    }
+
+    /// All cells with their position, row by row.
+    pub fn enumerate(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The orthogonal neighbors of a position that are part of the grid.
+    pub fn neighbors_4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBORS_4
+            .into_iter()
+            .filter_map(move |delta| self.offset(pos, delta))
+    }
+
+    /// The orthogonal and diagonal neighbors of a position that are part of the grid.
+    pub fn neighbors_8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBORS_8
+            .into_iter()
+            .filter_map(move |delta| self.offset(pos, delta))
+    }
+
+    /// Positions from `pos` (inclusive) in steps of `(dx, dy)`, until leaving the grid.
+    pub fn ray(
+        &self,
+        pos: (usize, usize),
+        (dx, dy): (isize, isize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        std::iter::successors(Some(pos), move |&pos| self.offset(pos, (dx, dy)))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    /// The cells from `pos` towards the bottom right.
+    pub fn diagonal(&self, pos: (usize, usize)) -> impl Iterator<Item = &T> {
+        self.ray(pos, (1, 1)).map(|pos| &self[pos])
+    }
+
+    /// The cells from `pos` towards the bottom left.
+    pub fn anti_diagonal(&self, pos: (usize, usize)) -> impl Iterator<Item = &T> {
+        self.ray(pos, (-1, 1)).map(|pos| &self[pos])
+    }
+
+    /// The position of the first cell equal to `value`, row by row.
+    pub fn find(&self, value: &T) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        self.enumerate()
+            .find(|(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+    }
+
+    /// The positions of all cells equal to `value`, row by row.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = (usize, usize)> + 'a
+    where
+        T: PartialEq,
+    {
+        self.enumerate()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+    }
+
+    /// A grid of the same size with every cell mapped.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        assert!(
+            x < self.width,
+            "x {x} is outside of a grid with width {}",
+            self.width
+        );
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        assert!(
+            x < self.width,
+            "x {x} is outside of a grid with width {}",
+            self.width
+        );
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+/// Prints one row per line, without separators between cells.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when parsing a [`Grid`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    /// The input has no rows.
+    Empty,
+    /// A row has a different width than the first one.
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Error for GridError {}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "the grid is empty"),
+            GridError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line} has {found} cells, expected {expected} like the first row"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, GridError};
+
+    fn example() -> Grid<char> {
+        Grid::parse("abc\ndef\n", |c| c).unwrap()
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid = example();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+
+        let indented = Grid::parse("\n    12\n    34\n    ", |c| c.to_digit(10).unwrap()).unwrap();
+        assert_eq!(indented.row(1), &[3, 4]);
+
+        assert_eq!(Grid::parse("", |c| c), Err(GridError::Empty));
+        assert_eq!(
+            Grid::parse("abc\nde", |c| c),
+            Err(GridError::Ragged {
+                line: 2,
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn checks_bounds() {
+        let mut grid = example();
+        assert_eq!(grid.get(0, 0), Some(&'a'));
+        assert_eq!(grid.get(-1, 0), None);
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+
+        *grid.get_mut(1, 1).unwrap() = 'x';
+        assert_eq!(grid.row(1), &['d', 'x', 'f']);
+    }
+
+    #[test]
+    fn iterates_neighbors() {
+        let grid = example();
+        let neighbors: Vec<_> = grid.neighbors_4((0, 0)).collect();
+        assert_eq!(neighbors, [(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbors_8((1, 0)).count(), 5);
+        assert_eq!(grid.neighbors_8((1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn views_lines() {
+        let grid = example();
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.diagonal((0, 0)).collect::<String>(), "ae");
+        assert_eq!(grid.anti_diagonal((2, 0)).collect::<String>(), "ce");
+        assert_eq!(grid.ray((2, 1), (-1, 0)).count(), 3);
+        assert_eq!(grid.rows().count(), 2);
+    }
+
+    #[test]
+    fn finds_values() {
+        let grid = Grid::parse("#.#\n.#.", |c| c).unwrap();
+        assert_eq!(grid.find(&'.'), Some((1, 0)));
+        assert_eq!(grid.find(&'x'), None);
+        assert_eq!(
+            grid.find_all(&'#').collect::<Vec<_>>(),
+            [(0, 0), (2, 0), (1, 1)]
+        );
+        assert_eq!(grid.map(|c| *c == '#').iter().filter(|c| **c).count(), 3);
+        assert_eq!(Grid::new(2, 2, 0).to_string(), "00\n00\n");
+    }
+}
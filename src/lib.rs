@@ -1,3 +1,14 @@
+pub mod bitset;
+pub mod components;
+pub mod cycle;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod math;
+pub mod parse;
+pub mod polygon;
+pub mod ranges;
 pub mod template;
+pub mod testing;
 
 // Use this file to add helper functions and additional modules.
@@ -1,94 +1,332 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, completions, download, inputs, new_year, read, scaffold, scale, solve, status, time, today,
+};
+use advent_of_code::template::config;
 use args::{parse, AppArguments};
 
-#[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
-
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        authors,
+        cli::{self, CommandSpec},
+        commands::{completions::Shell, inputs::Action},
+        config::Config,
+        Day, PuzzleId, Year,
+    };
+    use std::{ffi::OsString, fmt::Display};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
             download: bool,
             overwrite: bool,
+            author: Option<String>,
         },
         Solve {
-            day: Day,
+            puzzle: PuzzleId,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            all_inputs: bool,
+            author: Option<String>,
+            compare: bool,
+            variants: bool,
         },
         All {
+            year: Option<Year>,
             release: bool,
         },
         Time {
+            year: Option<Year>,
             all: bool,
             day: Option<Day>,
             store: bool,
+            profile: Option<String>,
+        },
+        Scale {
+            puzzle: PuzzleId,
+        },
+        Status {
+            run: bool,
+        },
+        Completions {
+            shell: Shell,
         },
-        #[cfg(feature = "today")]
-        Today,
+        Inputs {
+            action: Action,
+        },
+        NewYear {
+            year: Year,
+        },
+        Help(String),
+        Today {
+            wait: bool,
+        },
+    }
+
+    /// Appends the default flags configured for the subcommand, unless they were passed explicitly.
+    /// A default flag like `--release` is turned off by passing `--no-release`.
+    fn with_default_flags(mut args: Vec<OsString>, config: &Config) -> Vec<OsString> {
+        let Some(command) = args.first().and_then(|c| c.to_str()) else {
+            return args;
+        };
+
+        let defaults = config.default_flags(command);
+        let mut i = 0;
+        while i < defaults.len() {
+            let flag = &defaults[i];
+            // a default like `--submit 1` carries a value that has to be skipped alongside its flag.
+            let has_value = defaults
+                .get(i + 1)
+                .is_some_and(|next| !next.starts_with('-'));
+            let len = if has_value { 2 } else { 1 };
+
+            let negated = flag
+                .strip_prefix("--")
+                .map(|name| OsString::from(format!("--no-{name}")));
+            let turned_off = negated.is_some_and(|negated| {
+                let before = args.len();
+                args.retain(|arg| *arg != negated);
+                args.len() < before
+            });
+
+            if !turned_off && !args.iter().any(|arg| arg == flag.as_str()) {
+                args.extend(defaults[i..i + len].iter().map(OsString::from));
+            }
+            i += len;
+        }
+
+        args
+    }
+
+    /// An invalid invocation. Printed together with the usage of the affected command.
+    pub struct UsageError {
+        command: Option<&'static CommandSpec>,
+        message: String,
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+    impl UsageError {
+        fn new(command: Option<&'static CommandSpec>, message: impl Into<String>) -> Self {
+            Self {
+                command,
+                message: message.into(),
+            }
+        }
+    }
+
+    impl Display for UsageError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            writeln!(f, "Error: {}", self.message)?;
+            writeln!(f)?;
+            match self.command {
+                Some(command) => {
+                    writeln!(f, "Usage: {}", command.usage())?;
+                    write!(
+                        f,
+                        "Run `cargo {} --help` for more information.",
+                        command.name
+                    )
+                }
+                None => write!(f, "{}", cli::overview()),
+            }
+        }
+    }
+
+    pub fn parse(config: &Config) -> Result<AppArguments, UsageError> {
+        let args = with_default_flags(std::env::args_os().skip(1).collect(), config);
+        let mut args = pico_args::Arguments::from_vec(args);
+
+        let subcommand = args
+            .subcommand()
+            .map_err(|e| UsageError::new(None, e.to_string()))?;
+
+        let Some(subcommand) = subcommand else {
+            return Err(UsageError::new(None, "no command specified."));
+        };
+
+        if subcommand == "help" || subcommand == "--help" || subcommand == "-h" {
+            let help = match args.opt_free_from_str::<String>().ok().flatten() {
+                Some(name) => cli::find(&name)
+                    .ok_or_else(|| UsageError::new(None, format!("unknown command `{name}`.")))?
+                    .help(),
+                None => cli::overview(),
+            };
+            return Ok(AppArguments::Help(help));
+        }
+
+        let Some(spec) = cli::find(&subcommand) else {
+            return Err(UsageError::new(
+                None,
+                format!("unknown command `{subcommand}`."),
+            ));
+        };
+
+        if args.contains(["-h", "--help"]) {
+            return Ok(AppArguments::Help(spec.help()));
+        }
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
+        let err = |e: pico_args::Error| UsageError::new(Some(spec), e.to_string());
+
+        // `--year` is shared by all commands that operate on puzzles.
+        let year: Option<Year> = if spec.flags.iter().any(|f| f.name == "--year") {
+            args.opt_value_from_str("--year").map_err(err)?
+        } else {
+            None
+        };
+        // days are validated against the event of the selected year, which might have less than 25 days.
+        let check_day = |day: Day| {
+            let event = year.unwrap_or_else(Year::current);
+            if event.contains(day.into_inner()) {
+                Ok(day)
+            } else {
+                Err(UsageError::new(
+                    Some(spec),
+                    format!(
+                        "day {} is not part of the {event} event, which has {} days.",
+                        day.into_inner(),
+                        event.days()
+                    ),
+                ))
+            }
+        };
+        let puzzle = |day: Day| check_day(day).map(|day| PuzzleId::from_opt(year, day));
+        // authors are module names in `src/bin/NN/`.
+        let check_author = |author: Option<String>| {
+            match author {
+            Some(author) if !authors::is_valid_name(&author) => Err(UsageError::new(
+                Some(spec),
+                format!("author `{author}` is not a valid module name, use lowercase letters, digits and underscores, but not `main` or a Rust keyword."),
+            )),
+            author => Ok(author),
+        }
+        };
+
+        // NOTE: flags are consumed before positional arguments, so their order on the command-line does not matter.
+        let app_args = match spec.name {
+            "all" => AppArguments::All {
+                year,
                 release: args.contains("--release"),
             },
-            Some("time") => {
+            "time" => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let profile = args.opt_value_from_str("--host").map_err(err)?;
 
                 AppArguments::Time {
+                    year,
                     all,
-                    day: args.opt_free_from_str()?,
                     store,
+                    profile,
+                    day: args
+                        .opt_free_from_str()
+                        .map_err(err)?
+                        .map(check_day)
+                        .transpose()?,
                 }
             }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+            "download" => AppArguments::Download {
+                puzzle: puzzle(args.free_from_str().map_err(err)?)?,
             },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+            "read" => AppArguments::Read {
+                puzzle: puzzle(args.free_from_str().map_err(err)?)?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+            "scaffold" => AppArguments::Scaffold {
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                author: check_author(args.opt_value_from_str("--author").map_err(err)?)?,
+                puzzle: puzzle(args.free_from_str().map_err(err)?)?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
-            #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
+            "solve" => {
+                let release = args.contains("--release");
+                let dhat = args.contains("--dhat");
+                let submit: Option<u8> = args.opt_value_from_str("--submit").map_err(err)?;
+
+                let all_inputs = args.contains("--all-inputs");
+                let author = check_author(args.opt_value_from_str("--author").map_err(err)?)?;
+                let compare = args.contains("--compare");
+                let variants = args.contains("--variants");
+
+                if submit.is_some_and(|part| part != 1 && part != 2) {
+                    return Err(UsageError::new(
+                        Some(spec),
+                        "`--submit` expects part 1 or 2.",
+                    ));
+                }
+
+                if all_inputs && (dhat || submit.is_some()) {
+                    return Err(UsageError::new(
+                        Some(spec),
+                        "`--all-inputs` can not be combined with `--dhat` or `--submit`.",
+                    ));
+                }
+
+                if compare
+                    && (dhat || submit.is_some() || all_inputs || author.is_some() || variants)
+                {
+                    return Err(UsageError::new(
+                        Some(spec),
+                        "`--compare` runs all authors and can not be combined with other flags except `--release`.",
+                    ));
+                }
+
+                if variants && (dhat || submit.is_some() || all_inputs || author.is_some()) {
+                    return Err(UsageError::new(
+                        Some(spec),
+                        "`--variants` can not be combined with `--dhat`, `--submit`, `--all-inputs` or `--author`.",
+                    ));
+                }
+
+                AppArguments::Solve {
+                    release,
+                    dhat,
+                    submit,
+                    all_inputs,
+                    author,
+                    compare,
+                    variants,
+                    puzzle: puzzle(args.free_from_str().map_err(err)?)?,
+                }
             }
+            "scale" => AppArguments::Scale {
+                puzzle: puzzle(args.free_from_str().map_err(err)?)?,
+            },
+            "status" => AppArguments::Status {
+                run: !args.contains("--no-run"),
+            },
+            "inputs" => AppArguments::Inputs {
+                action: args
+                    .free_from_fn(|s: &str| s.parse::<Action>())
+                    .map_err(err)?,
+            },
+            "new-year" => AppArguments::NewYear {
+                year: args.free_from_str().map_err(err)?,
+            },
+            "completions" => AppArguments::Completions {
+                shell: args
+                    .free_from_fn(|s: &str| s.parse::<Shell>())
+                    .map_err(err)?,
+            },
+            "today" => AppArguments::Today {
+                wait: args.contains("--wait"),
+            },
+            _ => unreachable!("every command spec has a matching parser"),
         };
 
         let remaining = args.finish();
         if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+            let remaining: Vec<_> = remaining
+                .iter()
+                .map(|arg| arg.to_string_lossy().to_string())
+                .collect();
+            return Err(UsageError::new(
+                Some(spec),
+                format!("unexpected argument(s): {}.", remaining.join(" ")),
+            ));
         }
 
         Ok(app_args)
@@ -96,49 +334,67 @@ mod args {
 }
 
 fn main() {
-    match parse() {
+    let config = match config::init() {
+        Ok(config) => config,
         Err(err) => {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
+    };
+
+    match parse(config) {
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(2);
+        }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
+            AppArguments::All { year, release } => all::handle(year, release),
+            AppArguments::Time {
+                year,
                 day,
+                all,
+                store,
+                profile,
+            } => time::handle(year, day, all, store, profile.as_deref()),
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Scaffold {
+                puzzle,
                 download,
                 overwrite,
+                author,
             } => {
-                scaffold::handle(day, overwrite);
+                scaffold::handle(puzzle, overwrite, author.as_deref());
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle);
                 }
             }
             AppArguments::Solve {
-                day,
+                puzzle,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+                all_inputs,
+                author,
+                compare,
+                variants,
+            } => {
+                let author = author.as_deref();
+                if compare {
+                    solve::handle_compare(puzzle, release);
+                } else if all_inputs {
+                    solve::handle_all_inputs(puzzle, release, author);
+                } else {
+                    solve::handle(puzzle, release, dhat, submit, author, variants);
+                }
             }
+            AppArguments::Scale { puzzle } => scale::handle(puzzle),
+            AppArguments::Status { run } => status::handle(run),
+            AppArguments::Completions { shell } => completions::handle(shell),
+            AppArguments::Inputs { action } => inputs::handle(action),
+            AppArguments::NewYear { year } => new_year::handle(year),
+            AppArguments::Help(help) => println!("{help}"),
+            AppArguments::Today { wait } => today::handle(wait),
         },
     };
 }
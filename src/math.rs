@@ -0,0 +1,459 @@
+/// Exact integer math: gcd and lcm, modular arithmetic, the Chinese remainder theorem, rationals and a solver for
+/// small linear systems.
+///
+/// Intermediate products are computed in `i128`, so inputs anywhere in the `i64` range do not overflow silently.
+use std::{
+    error::Error,
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+/// The greatest common divisor. `gcd(0, 0)` is 0.
+///
+/// Unsigned, as `gcd(i64::MIN, 0)` does not fit into an `i64`.
+pub fn gcd(a: i64, b: i64) -> u64 {
+    gcd_i128(a.into(), b.into()) as u64
+}
+
+fn gcd_i128(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+/// The least common multiple, always non-negative.
+///
+/// # Panics
+///
+/// If the result does not fit into an `i64`.
+pub fn lcm(a: i64, b: i64) -> i64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    let lcm = (i128::from(a) / gcd_i128(a.into(), b.into()) * i128::from(b)).abs();
+    i64::try_from(lcm).unwrap_or_else(|_| panic!("lcm({a}, {b}) does not fit into an i64"))
+}
+
+/// Extended Euclid: `(g, x, y)` with `a * x + b * y == g == gcd(a, b)`. `g` is unsigned like in [`gcd`].
+pub fn ext_gcd(a: i64, b: i64) -> (u64, i64, i64) {
+    let (mut old_r, mut r) = (i128::from(a), i128::from(b));
+    let (mut old_x, mut x) = (1_i128, 0_i128);
+    let (mut old_y, mut y) = (0_i128, 1_i128);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (old_r, old_x, old_y) = (-old_r, -old_x, -old_y);
+    }
+    // |x| <= |b / g| and |y| <= |a / g| for the coefficients Euclid finds, so only `g` can exceed an `i64`.
+    (old_r as u64, old_x as i64, old_y as i64)
+}
+
+/// The inverse of `a` modulo `m`, in `0..m`. `None` if `a` and `m` are not coprime.
+///
+/// # Panics
+///
+/// If `m` is not positive.
+pub fn mod_inv(a: i64, m: i64) -> Option<i64> {
+    assert!(m > 0, "modulus {m} is not positive");
+    let (g, x, _) = ext_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// The Chinese remainder theorem: the `x` with `x ≡ r (mod m)` for every `(r, m)`, as `(x, lcm of all m)` with
+/// `x` in `0..lcm`. Moduli do not need to be coprime. `None` if the congruences contradict each other or the
+/// combined modulus does not fit into an `i64`.
+///
+/// # Panics
+///
+/// If a modulus is not positive.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let (mut x, mut modulus) = (0_i128, 1_i128);
+
+    for &(r, m) in congruences {
+        assert!(m > 0, "modulus {m} is not positive");
+        let (r, m) = (i128::from(r).rem_euclid(m.into()), i128::from(m));
+
+        // x + modulus * k ≡ r (mod m) has a solution for k iff gcd(modulus, m) divides the difference.
+        let g = gcd_i128(modulus, m);
+        let diff = r - x;
+        if diff % g != 0 {
+            return None;
+        }
+
+        let step = m / g;
+        let inv = mod_inv(
+            i64::try_from((modulus / g) % step).ok()?,
+            step.try_into().ok()?,
+        )?;
+        let k = (diff / g).rem_euclid(step) * i128::from(inv) % step;
+        x += modulus * k;
+        modulus = i64::try_from(modulus * step).ok()?.into();
+        x = x.rem_euclid(modulus);
+    }
+
+    Some((x as i64, modulus as i64))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An exact fraction, always reduced and with a positive denominator.
+/// The operators panic on overflow, use the `checked_` methods to handle it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numer: i128,
+    denom: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { numer: 0, denom: 1 };
+    pub const ONE: Rational = Rational { numer: 1, denom: 1 };
+
+    /// # Panics
+    ///
+    /// If `denom` is 0.
+    pub fn new(numer: i128, denom: i128) -> Self {
+        assert!(denom != 0, "denominator is 0");
+        let g = gcd_i128(numer, denom);
+        let sign = denom.signum();
+        Self {
+            numer: sign * numer / g,
+            denom: sign * denom / g,
+        }
+    }
+
+    pub fn numer(self) -> i128 {
+        self.numer
+    }
+
+    pub fn denom(self) -> i128 {
+        self.denom
+    }
+
+    pub fn is_integer(self) -> bool {
+        self.denom == 1
+    }
+
+    /// The value as an integer, if it is one.
+    pub fn to_integer(self) -> Option<i128> {
+        self.is_integer().then_some(self.numer)
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let g = gcd_i128(self.denom, other.denom);
+        let numer = self
+            .numer
+            .checked_mul(other.denom / g)?
+            .checked_add(other.numer.checked_mul(self.denom / g)?)?;
+        Some(Self::new(numer, (self.denom / g).checked_mul(other.denom)?))
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.checked_add(other.checked_neg()?)
+    }
+
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        // cross-reduce first to keep the products small.
+        let g1 = gcd_i128(self.numer, other.denom).max(1);
+        let g2 = gcd_i128(other.numer, self.denom).max(1);
+        let numer = (self.numer / g1).checked_mul(other.numer / g2)?;
+        let denom = (self.denom / g2).checked_mul(other.denom / g1)?;
+        Some(Self::new(numer, denom))
+    }
+
+    /// `None` on overflow or division by zero.
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        self.checked_mul(other.checked_recip()?)
+    }
+
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Self {
+            numer: self.numer.checked_neg()?,
+            denom: self.denom,
+        })
+    }
+
+    /// `1 / self`, `None` for zero.
+    pub fn checked_recip(self) -> Option<Self> {
+        (self.numer != 0).then(|| Self::new(self.denom, self.numer))
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Self::new(value.into(), 1)
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(value: i128) -> Self {
+        Self::new(value, 1)
+    }
+}
+
+impl Add for Rational {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.checked_add(other)
+            .expect("rational addition overflowed")
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.checked_sub(other)
+            .expect("rational subtraction overflowed")
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.checked_mul(other)
+            .expect("rational multiplication overflowed")
+    }
+}
+
+impl Div for Rational {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        assert!(other.numer != 0, "division by zero");
+        self.checked_div(other)
+            .expect("rational division overflowed")
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.checked_neg().expect("rational negation overflowed")
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Solves `coefficients * x = constants` exactly by Gaussian elimination, e.g. `a * 94 + b * 22 = 8400` and
+/// `a * 34 + b * 67 = 5400` for day 13 is `solve_linear(&[vec![94, 22], vec![34, 67]], &[8400, 5400])`.
+/// Every row of `coefficients` is one equation, so there may be more equations than unknowns.
+///
+/// # Panics
+///
+/// If the rows have different lengths, or there are not as many constants as equations.
+pub fn solve_linear(
+    coefficients: &[Vec<i64>],
+    constants: &[i64],
+) -> Result<Vec<Rational>, SolveError> {
+    assert_eq!(
+        coefficients.len(),
+        constants.len(),
+        "every equation needs one constant"
+    );
+    let unknowns = coefficients.first().map_or(0, Vec::len);
+    assert!(
+        coefficients.iter().all(|row| row.len() == unknowns),
+        "all equations need the same number of coefficients"
+    );
+
+    // augmented matrix, the constants are the last column.
+    let mut rows: Vec<Vec<Rational>> = coefficients
+        .iter()
+        .zip(constants)
+        .map(|(row, &constant)| {
+            row.iter()
+                .chain([&constant])
+                .map(|&value| Rational::from(value))
+                .collect()
+        })
+        .collect();
+
+    // reduced row echelon form, one pivot per unknown as long as there is a row with a non-zero coefficient.
+    let mut rank = 0;
+    for column in 0..unknowns {
+        let Some(pivot) = (rank..rows.len()).find(|&row| rows[row][column] != Rational::ZERO)
+        else {
+            continue;
+        };
+        rows.swap(rank, pivot);
+
+        let factor = rows[rank][column];
+        for value in rows[rank].iter_mut() {
+            *value = value.checked_div(factor).ok_or(SolveError::Overflow)?;
+        }
+
+        let pivot_row = rows[rank].clone();
+        for (row, values) in rows.iter_mut().enumerate() {
+            let factor = values[column];
+            if row == rank || factor == Rational::ZERO {
+                continue;
+            }
+            for (value, &pivot_value) in values.iter_mut().zip(&pivot_row).skip(column) {
+                let scaled = pivot_value
+                    .checked_mul(factor)
+                    .ok_or(SolveError::Overflow)?;
+                *value = value.checked_sub(scaled).ok_or(SolveError::Overflow)?;
+            }
+        }
+        rank += 1;
+    }
+
+    // rows without a pivot read 0 = constant.
+    if rows[rank..]
+        .iter()
+        .any(|row| row[unknowns] != Rational::ZERO)
+    {
+        return Err(SolveError::Inconsistent);
+    }
+    if rank < unknowns {
+        return Err(SolveError::Underdetermined);
+    }
+
+    Ok(rows
+        .iter()
+        .take(unknowns)
+        .map(|row| row[unknowns])
+        .collect())
+}
+
+/// An error which is returned when a linear system has no unique solution.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SolveError {
+    /// No values satisfy all equations.
+    Inconsistent,
+    /// Infinitely many values satisfy all equations.
+    Underdetermined,
+    /// An intermediate value does not fit into an `i128`.
+    Overflow,
+}
+
+impl Error for SolveError {}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::Inconsistent => write!(f, "the equations have no solution"),
+            SolveError::Underdetermined => {
+                write!(f, "the equations have infinitely many solutions")
+            }
+            SolveError::Overflow => write!(f, "an intermediate value overflowed"),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{crt, ext_gcd, gcd, lcm, mod_inv, solve_linear, Rational, SolveError};
+
+    #[test]
+    fn computes_divisors() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(-4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+
+        let (g, x, y) = ext_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+
+        // 2^63 is one more than `i64::MAX`.
+        assert_eq!(gcd(i64::MIN, 0), 1 << 63);
+        assert_eq!(gcd(i64::MIN, i64::MIN), 1 << 63);
+        assert_eq!(gcd(i64::MIN, -6), 2);
+        assert_eq!(gcd(i64::MIN, i64::MAX), 1);
+        for (a, b) in [
+            (i64::MIN, 0),
+            (i64::MIN, i64::MIN),
+            (i64::MIN, 3),
+            (i64::MAX, i64::MIN),
+        ] {
+            let (g, x, y) = ext_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(
+                i128::from(a) * i128::from(x) + i128::from(b) * i128::from(y),
+                i128::from(g)
+            );
+        }
+
+        assert_eq!(mod_inv(3, 11), Some(4));
+        assert_eq!(mod_inv(-3, 11), Some(7));
+        assert_eq!(mod_inv(4, 8), None);
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt(&[]), Some((0, 1)));
+
+        let big = 1_000_000_007;
+        assert_eq!(
+            crt(&[(5, big), (7, 998_244_353)]).map(|(_, m)| m),
+            Some(big * 998_244_353)
+        );
+        assert_eq!(crt(&[(0, big), (0, 998_244_353), (0, 999_999_937)]), None);
+    }
+
+    #[test]
+    fn does_rational_arithmetic() {
+        let half = Rational::new(2, 4);
+        assert_eq!(half, Rational::new(-1, -2));
+        assert_eq!((half.numer(), half.denom()), (1, 2));
+        assert_eq!(half + Rational::new(1, 3), Rational::new(5, 6));
+        assert_eq!(half - Rational::ONE, Rational::new(-1, 2));
+        assert_eq!(half * Rational::from(4_i64), Rational::from(2_i64));
+        assert_eq!((half / Rational::new(1, 3)).to_string(), "3/2");
+        assert_eq!(Rational::new(6, 3).to_integer(), Some(2));
+        assert_eq!(half.to_integer(), None);
+        assert_eq!(Rational::ZERO.checked_recip(), None);
+        assert_eq!(Rational::from(i128::MAX).checked_add(Rational::ONE), None);
+    }
+
+    #[test]
+    fn solves_linear_systems() {
+        let solution = solve_linear(&[vec![94, 22], vec![34, 67]], &[8400, 5400]).unwrap();
+        assert_eq!(solution, [Rational::from(80_i64), Rational::from(40_i64)]);
+
+        let solution = solve_linear(
+            &[vec![0, 1, 1], vec![2, 0, 1], vec![1, 1, 0], vec![1, 1, 1]],
+            &[5, 5, 3, 6],
+        )
+        .unwrap();
+        assert_eq!(solution, [1_i64, 2, 3].map(Rational::from));
+        let halves = solve_linear(&[vec![2]], &[1]).unwrap();
+        assert_eq!(halves, [Rational::new(1, 2)]);
+
+        assert_eq!(
+            solve_linear(&[vec![1, 2], vec![2, 4]], &[3, 6]),
+            Err(SolveError::Underdetermined)
+        );
+        assert_eq!(
+            solve_linear(&[vec![1, 2], vec![2, 4]], &[3, 7]),
+            Err(SolveError::Inconsistent)
+        );
+    }
+}
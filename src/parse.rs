@@ -0,0 +1,315 @@
+/// Helpers for parsing puzzle inputs.
+///
+/// For quick extraction, [`ints`] finds all numbers in a line and [`paragraphs`] splits blocks separated by blank lines.
+/// For strict parsing, the nom combinators below are run with [`parse_lines`] or [`parse_all`], which report the
+/// line and column of the first unexpected character instead of dropping lines silently.
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use nom::{
+    bytes::complete::{tag, take_while1},
+    character::complete::{char, digit1, line_ending, multispace0, space0, space1},
+    combinator::{all_consuming, map_res, opt, recognize},
+    multi::separated_list1,
+    sequence::{delimited, pair, separated_pair, terminated},
+    IResult, Parser,
+};
+
+use crate::grid::Grid;
+
+/// All integers in a line, in order. A `-` directly before a number is its sign, unless it follows a digit
+/// (like in the range `3-5`) or `T` is unsigned.
+///
+/// # Panics
+///
+/// If a number does not fit into `T`.
+pub fn ints<T: FromStr>(line: &str) -> Vec<T> {
+    let bytes = line.as_bytes();
+    let mut numbers = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let mut start = i;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if start > 0
+            && bytes[start - 1] == b'-'
+            && (start < 2 || !bytes[start - 2].is_ascii_digit())
+        {
+            start -= 1;
+        }
+
+        let token = &line[start..i];
+        let number = token
+            .parse()
+            .or_else(|_| token.trim_start_matches('-').parse())
+            .unwrap_or_else(|_| panic!("could not parse `{token}` as a number"));
+        numbers.push(number);
+    }
+
+    numbers
+}
+
+/// Blocks of lines separated by blank lines, e.g. rules and updates in day 5. Blocks are trimmed.
+pub fn paragraphs(input: &str) -> impl Iterator<Item = &str> {
+    let mut rest = input;
+
+    std::iter::from_fn(move || loop {
+        if rest.is_empty() {
+            return None;
+        }
+
+        // a blank line is a line break followed by optional whitespace and another line break.
+        let mut end = rest.len();
+        let mut next = rest.len();
+        let mut search = 0;
+        while let Some(offset) = rest[search..].find('\n') {
+            let line_end = search + offset;
+            let after = &rest[line_end + 1..];
+            let blank = after.len() - after.trim_start_matches([' ', '\t', '\r']).len();
+            if after[blank..].starts_with('\n') {
+                end = line_end;
+                next = line_end + 1 + blank + 1;
+                break;
+            }
+            search = line_end + 1;
+        }
+
+        let block = rest[..end].trim();
+        rest = &rest[next..];
+        if !block.is_empty() {
+            return Some(block);
+        }
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An integer with an optional minus sign.
+pub fn number<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(char('-')), digit1)), str::parse)(input)
+}
+
+/// Numbers separated by `separator`, which may be surrounded by spaces. A blank separator means any number of spaces.
+pub fn number_list<'a, T: FromStr>(
+    separator: &'a str,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>> {
+    move |input| {
+        let separator = separator.trim();
+        if separator.is_empty() {
+            separated_list1(space1, number)(input)
+        } else {
+            separated_list1(delimited(space0, tag(separator), space0), number)(input)
+        }
+    }
+}
+
+/// A key and a value separated by `separator`, e.g. `190: 10 19` with `key_value(number, ":", number_list(" "))`.
+pub fn key_value<'a, K, V>(
+    key: impl Parser<&'a str, K, nom::error::Error<&'a str>>,
+    separator: &'a str,
+    value: impl Parser<&'a str, V, nom::error::Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (K, V)> {
+    separated_pair(key, delimited(space0, tag(separator), space0), value)
+}
+
+/// A block of lines with one cell per char, up to the first line with a char that `cell` rejects.
+pub fn char_grid<'a, T>(
+    cell: impl Fn(char) -> Option<T>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Grid<T>> {
+    move |input| {
+        let line = take_while1(|c: char| c != '\n' && c != '\r' && cell(c).is_some());
+        map_res(recognize(separated_list1(line_ending, line)), |block| {
+            Grid::parse(block, |c| {
+                cell(c).expect("checked while recognizing the block")
+            })
+        })(input)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Parses every non-blank line of `input` with `parser`, which has to consume the whole line.
+/// Line numbers in errors are relative to `input`.
+pub fn parse_lines<'a, T>(
+    input: &'a str,
+    mut parser: impl Parser<&'a str, T, nom::error::Error<&'a str>>,
+) -> Result<Vec<T>, ParseError> {
+    let mut items = vec![];
+
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            continue;
+        }
+
+        match all_consuming(|i| parser.parse(i))(line) {
+            Ok((_, item)) => items.push(item),
+            Err(err) => {
+                let rest = remaining(&err, line);
+                return Err(ParseError::at(line, line.len() - rest.len(), index + 1));
+            }
+        }
+    }
+
+    Ok(items)
+}
+
+/// Parses all of `input` with `parser`. Trailing whitespace is allowed.
+pub fn parse_all<'a, T>(
+    input: &'a str,
+    parser: impl Parser<&'a str, T, nom::error::Error<&'a str>>,
+) -> Result<T, ParseError> {
+    match all_consuming(terminated(parser, multispace0))(input) {
+        Ok((_, value)) => Ok(value),
+        Err(err) => {
+            let offset = input.len() - remaining(&err, input).len();
+            let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+            let line_end = input[offset..]
+                .find('\n')
+                .map_or(input.len(), |i| offset + i);
+            let line = input[..offset].matches('\n').count() + 1;
+            Err(ParseError::at(
+                input[line_start..line_end].trim_end(),
+                offset - line_start,
+                line,
+            ))
+        }
+    }
+}
+
+/// The input left when a parser failed.
+fn remaining<'a>(err: &nom::Err<nom::error::Error<&'a str>>, input: &'a str) -> &'a str {
+    match err {
+        nom::Err::Error(e) | nom::Err::Failure(e) => e.input,
+        nom::Err::Incomplete(_) => &input[input.len()..],
+    }
+}
+
+/// An error which is returned when an input does not match a parser.
+/// `Debug` prints the same message as `Display`, so `unwrap()` shows where parsing failed.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column of the first unexpected char.
+    pub column: usize,
+    /// The offending line.
+    pub text: String,
+}
+
+impl ParseError {
+    fn at(text: &str, offset: usize, line: usize) -> Self {
+        // parsers backtrack to before separators, point at the char after them instead.
+        let offset = offset.min(text.len());
+        let offset = text.len() - text[offset..].trim_start_matches([' ', '\t']).len();
+        Self {
+            line,
+            column: text[..offset].chars().count() + 1,
+            text: text.to_string(),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let unexpected: String = self.text.chars().skip(self.column - 1).take(10).collect();
+        if unexpected.is_empty() {
+            write!(
+                f,
+                "line {}, column {}: unexpected end of line",
+                self.line, self.column
+            )?;
+        } else {
+            write!(
+                f,
+                "line {}, column {}: unexpected `{unexpected}`",
+                self.line, self.column
+            )?;
+        }
+        write!(f, "\n  {}\n  {}^", self.text, " ".repeat(self.column - 1))
+    }
+}
+
+impl std::fmt::Debug for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        char_grid, ints, key_value, number, number_list, paragraphs, parse_all, parse_lines,
+    };
+    use nom::{bytes::complete::tag, sequence::separated_pair};
+
+    #[test]
+    fn extracts_ints() {
+        assert_eq!(ints::<i64>("Button A: X+94, Y-34"), [94, -34]);
+        assert_eq!(ints::<i32>("p=0,4 v=-3,-3"), [0, 4, -3, -3]);
+        assert_eq!(ints::<u32>("3-5, -7"), [3, 5, 7]);
+        assert_eq!(ints::<i32>("3-5"), [3, 5]);
+        assert!(ints::<u8>("no numbers").is_empty());
+    }
+
+    #[test]
+    fn splits_paragraphs() {
+        let input = "a|b\nc|d\n\n1,2\n  \r\n3,4\n\n\n";
+        let blocks: Vec<_> = paragraphs(input).collect();
+        assert_eq!(blocks, ["a|b\nc|d", "1,2", "3,4"]);
+
+        let windows: Vec<_> = paragraphs("x\r\n\r\ny\r\n").collect();
+        assert_eq!(windows, ["x", "y"]);
+        assert_eq!(paragraphs("\n\n").count(), 0);
+    }
+
+    #[test]
+    fn parses_lines() {
+        let rules = parse_lines("47|53\n97|13\n", separated_pair(number, tag("|"), number));
+        assert_eq!(rules, Ok(vec![(47_u32, 53_u32), (97, 13)]));
+
+        let equations = parse_lines(
+            "190: 10 19\n3267: 81 40 27",
+            key_value(number, ":", number_list(" ")),
+        );
+        assert_eq!(equations.unwrap()[1], (3267_u64, vec![81, 40, 27]));
+
+        let err = parse_lines::<Vec<u32>>("7 6 4\n1 2 x 8\n", number_list(" ")).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 5: unexpected `x 8`\n  1 2 x 8\n      ^"
+        );
+    }
+
+    #[test]
+    fn parses_blocks() {
+        let input = "#.\n.#\n\n1,2,3\n";
+        let (grid, numbers) = parse_all(
+            input,
+            separated_pair(
+                char_grid(|c| matches!(c, '#' | '.').then_some(c == '#')),
+                tag("\n\n"),
+                number_list::<i32>(","),
+            ),
+        )
+        .unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert!(grid[(1, 1)]);
+        assert_eq!(numbers, [1, 2, 3]);
+
+        let err = parse_all("1,2\n3,x", number_list::<i32>(",")).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert!(err
+            .to_string()
+            .starts_with("line 2, column 1: unexpected `3,x`"));
+    }
+}
@@ -0,0 +1,294 @@
+/// Geometry of lattice polygons and of regions made of grid cells.
+///
+/// Polygons are lists of [`Point<i64>`] vertices in order, the last one connects back to the first.
+/// Regions are sets of cells given by a membership test, e.g. `|pos| garden.get(pos.x, pos.y) == Some(&'A')`.
+/// A region's outline is a rectilinear polygon through the corners of its cells.
+use crate::{
+    geometry::{Compass, Direction, Point},
+    math::gcd,
+};
+
+/// Twice the signed area by the shoelace formula. Positive if the vertices go clockwise, as `y` grows downwards.
+pub fn shoelace(vertices: &[Point<i64>]) -> i64 {
+    edges(vertices).map(|(a, b)| a.cross(b)).sum()
+}
+
+/// Twice the area, which is an integer for lattice polygons.
+pub fn double_area(vertices: &[Point<i64>]) -> i64 {
+    shoelace(vertices).abs()
+}
+
+/// The number of lattice points on the edges. For a rectilinear polygon this is also the length of its perimeter.
+pub fn boundary_points(vertices: &[Point<i64>]) -> i64 {
+    edges(vertices)
+        .map(|(a, b)| gcd(b.x - a.x, b.y - a.y) as i64)
+        .sum()
+}
+
+/// The number of lattice points strictly inside, by Pick's theorem: `area = interior + boundary / 2 - 1`.
+pub fn interior_points(vertices: &[Point<i64>]) -> i64 {
+    (double_area(vertices) - boundary_points(vertices) + 2) / 2
+}
+
+/// The vertices without the ones in the middle of a straight edge or repeated ones, so their number is the
+/// number of edges.
+pub fn simplify(vertices: &[Point<i64>]) -> Vec<Point<i64>> {
+    let mut distinct = vertices.to_vec();
+    distinct.dedup();
+    while distinct.len() > 1 && distinct.first() == distinct.last() {
+        distinct.pop();
+    }
+
+    let n = distinct.len();
+    (0..n)
+        .filter(|&i| {
+            let (prev, current, next) = (
+                distinct[(i + n - 1) % n],
+                distinct[i],
+                distinct[(i + 1) % n],
+            );
+            (current - prev).cross(next - current) != 0
+        })
+        .map(|i| distinct[i])
+        .collect()
+}
+
+/// The vertices of a dig plan or any other path given as steps from the origin.
+/// If the steps end at the origin, the result is a closed polygon.
+pub fn walk(steps: impl IntoIterator<Item = (Direction, i64)>) -> Vec<Point<i64>> {
+    let mut position = Point::new(0, 0);
+    let mut vertices = vec![];
+    for (direction, distance) in steps {
+        vertices.push(position);
+        let delta = direction.delta();
+        position += Point::new(delta.x as i64, delta.y as i64) * distance;
+    }
+    vertices
+}
+
+fn edges(vertices: &[Point<i64>]) -> impl Iterator<Item = (Point<i64>, Point<i64>)> + '_ {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(&a, &b)| (a, b))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A corner of a region's outline at one corner of a cell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Corner {
+    /// The outline turns around the cell: both cells next to the corner are outside.
+    Convex,
+    /// The outline turns into the cell: both cells next to the corner are inside, the diagonal one is outside.
+    Concave,
+    /// Like `Convex`, but the diagonal cell is inside as well, so two cells of the region touch at this corner.
+    Pinch,
+}
+
+/// The number of cell edges between the region and cells outside of it.
+pub fn perimeter(
+    cells: impl IntoIterator<Item = Point<isize>>,
+    contains: impl Fn(Point<isize>) -> bool,
+) -> usize {
+    cells
+        .into_iter()
+        .map(|cell| {
+            Direction::ALL
+                .into_iter()
+                .filter(|direction| !contains(cell + direction.delta()))
+                .count()
+        })
+        .sum()
+}
+
+/// The corners of the region's outline at the corners of `cell`, with the diagonal direction of each one.
+/// E.g. for the top-left cell `X` of `XX\nX.`, [`Compass::NW`] is convex and [`Compass::SE`] is concave.
+pub fn corners(
+    cell: Point<isize>,
+    contains: impl Fn(Point<isize>) -> bool,
+) -> impl Iterator<Item = (Compass, Corner)> {
+    Compass::DIAGONALS.into_iter().filter_map(move |diagonal| {
+        // the two cells orthogonally next to a diagonal, e.g. E and S for SE.
+        let before = contains(cell + diagonal.rotate_ccw().delta());
+        let after = contains(cell + diagonal.rotate_cw().delta());
+        let opposite = contains(cell + diagonal.delta());
+
+        let corner = match (before, after, opposite) {
+            (false, false, false) => Corner::Convex,
+            (false, false, true) => Corner::Pinch,
+            (true, true, false) => Corner::Concave,
+            _ => return None,
+        };
+        Some((diagonal, corner))
+    })
+}
+
+/// The number of straight sides of the region's outline, including the outlines of holes. It equals the number
+/// of corners, as every side ends in one.
+pub fn side_count(
+    cells: impl IntoIterator<Item = Point<isize>>,
+    contains: impl Fn(Point<isize>) -> bool,
+) -> usize {
+    cells
+        .into_iter()
+        .map(|cell| corners(cell, &contains).count())
+        .sum()
+}
+
+/// The number of holes in an orthogonally connected region, i.e. areas of other cells it encloses completely.
+/// Cells outside that touch diagonally belong to the same hole.
+pub fn hole_count(
+    cells: impl IntoIterator<Item = Point<isize>>,
+    contains: impl Fn(Point<isize>) -> bool,
+) -> usize {
+    // the Euler number (components - holes) is a quarter of the outer turns minus the inner turns of all outlines.
+    let mut turns = 0_isize;
+    let mut any = false;
+    for cell in cells {
+        any = true;
+        for (_, corner) in corners(cell, &contains) {
+            turns += match corner {
+                Corner::Convex | Corner::Pinch => 1,
+                Corner::Concave => -1,
+            };
+        }
+    }
+
+    if any {
+        (1 - turns / 4) as usize
+    } else {
+        0
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        boundary_points, corners, double_area, hole_count, interior_points, perimeter, shoelace,
+        side_count, simplify, walk, Corner,
+    };
+    use crate::{
+        components::label_components,
+        geometry::{Compass, Direction, Point},
+        grid::Grid,
+    };
+
+    /// The price of the fences of every region in a garden from day 12, by perimeter and by side count,
+    /// and the total number of holes.
+    fn prices(garden: &str) -> (usize, usize, usize) {
+        let garden = Grid::parse(garden, |c| c).unwrap();
+        let regions = label_components(&garden, |a, b| a == b);
+
+        let mut totals = (0, 0, 0);
+        for (region, cells) in regions.components.iter().zip(regions.cells()) {
+            let cells: Vec<_> = cells
+                .into_iter()
+                .map(|(x, y)| Point::new(x as isize, y as isize))
+                .collect();
+            let contains = |pos: Point<isize>| {
+                regions
+                    .labels
+                    .get(pos.x, pos.y)
+                    .is_some_and(|&label| label == region.label)
+            };
+
+            let fences = perimeter(cells.iter().copied(), contains);
+            assert_eq!(fences, region.perimeter);
+            totals.0 += fences * region.size;
+            totals.1 += side_count(cells.iter().copied(), contains) * region.size;
+            totals.2 += hole_count(cells.iter().copied(), contains);
+        }
+        totals
+    }
+
+    #[test]
+    fn measures_day_12_examples() {
+        assert_eq!(prices("AAAA\nBBCD\nBBCC\nEEEC"), (140, 80, 0));
+        assert_eq!(prices("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO"), (772, 436, 4));
+        assert_eq!(prices("EEEEE\nEXXXX\nEEEEE\nEXXXX\nEEEEE"), (692, 236, 0));
+        assert_eq!(
+            prices("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA"),
+            (1184, 368, 1)
+        );
+        assert_eq!(
+            prices(
+                "RRRRIICCFF\nRRRRIICCCF\nVVRRRCCFFF\nVVRCCCJFFF\nVVVVCJJCFE\n\
+                 VVIVCCJJEE\nVVIIICJJEE\nMIIIIIJJEE\nMIIISIJEEE\nMMMISSJEEE"
+            ),
+            (1930, 1206, 0)
+        );
+    }
+
+    #[test]
+    fn finds_corners() {
+        let region = Grid::parse("XX\nX.", |c| c == 'X').unwrap();
+        let contains = |pos: Point<isize>| region.get(pos.x, pos.y) == Some(&true);
+
+        let mut found: Vec<_> = corners(Point::new(0, 0), contains).collect();
+        found.sort_by_key(|&(diagonal, _)| diagonal);
+        assert_eq!(
+            found,
+            [
+                (Compass::SE, Corner::Concave),
+                (Compass::NW, Corner::Convex)
+            ]
+        );
+        assert_eq!(corners(Point::new(1, 0), contains).count(), 2);
+
+        let pinched = Grid::parse("X.\n.X", |c| c == 'X').unwrap();
+        let contains = |pos: Point<isize>| pinched.get(pos.x, pos.y) == Some(&true);
+        assert!(corners(Point::new(0, 0), contains).any(|c| c == (Compass::SE, Corner::Pinch)));
+    }
+
+    #[test]
+    fn measures_polygons() {
+        // the outline of a 4 x 3 block of cells.
+        let rectangle = [
+            Point::new(0, 0),
+            Point::new(4, 0),
+            Point::new(4, 3),
+            Point::new(0, 3),
+        ];
+        assert_eq!(shoelace(&rectangle), 24);
+        assert_eq!(double_area(&rectangle), 24);
+        assert_eq!(boundary_points(&rectangle), 14);
+        assert_eq!(interior_points(&rectangle), 3 * 2);
+
+        let mut reversed = rectangle;
+        reversed.reverse();
+        assert_eq!(shoelace(&reversed), -24);
+
+        let triangle = [Point::new(0, 0), Point::new(4, 0), Point::new(0, 4)];
+        assert_eq!(boundary_points(&triangle), 12);
+        assert_eq!(interior_points(&triangle), 3);
+    }
+
+    #[test]
+    fn walks_dig_plans() {
+        // the loop of the AoC 2023 day 18 example, 62 cubic meters including the trench.
+        let plan = "R6 D5 L2 D2 R2 D2 L5 U2 L1 U2 R2 U3 L2 U2";
+        let steps = plan.split(' ').map(|step| {
+            let direction = match &step[..1] {
+                "U" => Direction::Up,
+                "R" => Direction::Right,
+                "D" => Direction::Down,
+                _ => Direction::Left,
+            };
+            (direction, step[1..].parse().unwrap())
+        });
+        let vertices = walk(steps);
+        assert_eq!(vertices.len(), 14);
+        assert_eq!(interior_points(&vertices) + boundary_points(&vertices), 62);
+
+        let square = [
+            Point::new(0, 0),
+            Point::new(2, 0),
+            Point::new(4, 0),
+            Point::new(4, 4),
+            Point::new(4, 4),
+            Point::new(0, 4),
+        ];
+        assert_eq!(simplify(&square).len(), 4);
+    }
+}
@@ -0,0 +1,330 @@
+/// Sets of ranges and maps from ranges to values, for puzzles about spans like free disk space or seed ranges.
+///
+/// Ranges are half-open like `Range<T>`, so `2..5` covers 2, 3 and 4. Both collections keep their ranges disjoint
+/// and sorted in a `BTreeMap` keyed by the start, so lookups by position take O(log n). There is no index by length:
+/// [`RangeSet::first_fit`] scans the ranges in order.
+use std::{
+    collections::BTreeMap,
+    ops::{Add, Range, Sub},
+};
+
+/// A set of disjoint ranges. Overlapping or adjacent ranges are merged on insert.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RangeSet<T> {
+    ranges: BTreeMap<T, T>,
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> RangeSet<T> {
+    pub fn new() -> Self {
+        Self {
+            ranges: BTreeMap::new(),
+        }
+    }
+
+    /// The number of disjoint ranges.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Adds `range`, merging it with the ranges it overlaps or touches.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let (mut start, mut end) = (range.start, range.end);
+        while let Some((&other_start, &other_end)) = self.ranges.range(..=end).next_back() {
+            if other_end < start {
+                break;
+            }
+            self.ranges.remove(&other_start);
+            start = start.min(other_start);
+            end = end.max(other_end);
+        }
+        self.ranges.insert(start, end);
+    }
+
+    /// Removes `range`, splitting ranges that stick out on either side.
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        while let Some((&other_start, &other_end)) = self.ranges.range(..range.end).next_back() {
+            if other_end <= range.start {
+                break;
+            }
+            self.ranges.remove(&other_start);
+            if other_start < range.start {
+                self.ranges.insert(other_start, range.start);
+            }
+            if range.end < other_end {
+                self.ranges.insert(range.end, other_end);
+            }
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.ranges
+            .range(..=value)
+            .next_back()
+            .is_some_and(|(_, &end)| value < end)
+    }
+
+    /// All ranges in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.iter().map(|(&start, &end)| start..end)
+    }
+
+    /// The first span of length `len` that lies in a single range, e.g. the leftmost free space a file fits into.
+    /// Takes O(n), as it checks the ranges from the left until one is long enough.
+    pub fn first_fit(&self, len: T) -> Option<Range<T>> {
+        self.iter()
+            .find(|range| range.end - range.start >= len)
+            .map(|range| range.start..range.start + len)
+    }
+
+    /// The parts of `within` that are not covered, in ascending order.
+    pub fn gaps(&self, within: Range<T>) -> impl Iterator<Item = Range<T>> + '_ {
+        gaps(self.iter(), within)
+    }
+
+    /// Moves everything from `at` onwards into a new set, splitting a range that contains `at`.
+    pub fn split_off(&mut self, at: T) -> Self {
+        let mut after = self.ranges.split_off(&at);
+        if let Some((_, end)) = self.ranges.iter_mut().next_back() {
+            if *end > at {
+                after.insert(at, *end);
+                *end = at;
+            }
+        }
+        Self { ranges: after }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Extend<Range<T>> for RangeSet<T> {
+    /// Adds all ranges, e.g. of another set to merge both.
+    fn extend<I: IntoIterator<Item = Range<T>>>(&mut self, ranges: I) {
+        for range in ranges {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(ranges: I) -> Self {
+        let mut set = Self::new();
+        set.extend(ranges);
+        set
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A map from disjoint ranges to values, e.g. the files on a disk.
+/// Inserting a range overwrites the parts of other ranges it overlaps. Adjacent ranges with equal values are merged.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IntervalMap<T, V> {
+    intervals: BTreeMap<T, (T, V)>,
+}
+
+impl<T: Copy + Ord, V: Clone + PartialEq> IntervalMap<T, V> {
+    pub fn new() -> Self {
+        Self {
+            intervals: BTreeMap::new(),
+        }
+    }
+
+    /// The number of disjoint ranges.
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Maps every value in `range` to `value`.
+    pub fn insert(&mut self, range: Range<T>, value: V) {
+        if range.is_empty() {
+            return;
+        }
+        self.remove(range.clone());
+
+        let (mut start, mut end) = (range.start, range.end);
+        if let Some((&before, (before_end, before_value))) =
+            self.intervals.range(..start).next_back()
+        {
+            if *before_end == start && *before_value == value {
+                start = before;
+                self.intervals.remove(&before);
+            }
+        }
+        if let Some((after_end, after_value)) = self.intervals.get(&end) {
+            if *after_value == value {
+                let after = end;
+                end = *after_end;
+                self.intervals.remove(&after);
+            }
+        }
+        self.intervals.insert(start, (end, value));
+    }
+
+    /// Unmaps every value in `range`.
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        self.split_at(range.start);
+        self.split_at(range.end);
+
+        let inside: Vec<T> = self
+            .intervals
+            .range(range.start..range.end)
+            .map(|(&start, _)| start)
+            .collect();
+        for start in inside {
+            self.intervals.remove(&start);
+        }
+    }
+
+    /// Splits the range that contains `at` into two with the same value, so `at` is the start of a range.
+    pub fn split_at(&mut self, at: T) {
+        let Some((&start, (end, value))) = self.intervals.range(..at).next_back() else {
+            return;
+        };
+        if at < *end {
+            let (end, value) = (*end, value.clone());
+            self.intervals.insert(start, (at, value.clone()));
+            self.intervals.insert(at, (end, value));
+        }
+    }
+
+    pub fn get(&self, key: T) -> Option<&V> {
+        self.intervals
+            .range(..=key)
+            .next_back()
+            .filter(|(_, (end, _))| key < *end)
+            .map(|(_, (_, value))| value)
+    }
+
+    /// All ranges with their values, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = (Range<T>, &V)> + '_ {
+        self.intervals
+            .iter()
+            .map(|(&start, (end, value))| (start..*end, value))
+    }
+
+    /// The parts of `within` that are not mapped, in ascending order.
+    pub fn gaps(&self, within: Range<T>) -> impl Iterator<Item = Range<T>> + '_ {
+        gaps(self.iter().map(|(range, _)| range), within)
+    }
+}
+
+/// The parts of `within` between sorted, disjoint `ranges`.
+fn gaps<T: Copy + Ord>(
+    mut ranges: impl Iterator<Item = Range<T>>,
+    within: Range<T>,
+) -> impl Iterator<Item = Range<T>> {
+    let mut cursor = within.start;
+    std::iter::from_fn(move || {
+        while cursor < within.end {
+            let gap = match ranges.next() {
+                Some(range) if range.start < within.end => {
+                    let gap = cursor..range.start;
+                    cursor = cursor.max(range.end);
+                    gap
+                }
+                _ => {
+                    let gap = cursor..within.end;
+                    cursor = within.end;
+                    gap
+                }
+            };
+            if !gap.is_empty() {
+                return Some(gap);
+            }
+        }
+        None
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{IntervalMap, RangeSet};
+
+    #[test]
+    fn merges_and_splits_ranges() {
+        let mut set: RangeSet<i64> = [0..2, 5..7, 6..9, 9..10].into_iter().collect();
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0..2, 5..10]);
+        set.insert(1..5);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0..10]);
+
+        set.remove(3..5);
+        set.remove(8..12);
+        set.remove(-5..-1);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0..3, 5..8]);
+        assert!(set.contains(2) && !set.contains(3) && !set.contains(8));
+
+        set.remove(1..7);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0..1, 7..8]);
+
+        let mut set: RangeSet<i64> = [0..4, 6..9].into_iter().collect();
+        let after = set.split_off(2);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0..2]);
+        assert_eq!(after.iter().collect::<Vec<_>>(), [2..4, 6..9]);
+        set.extend(after.iter());
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn finds_space() {
+        let free: RangeSet<usize> = [2..5, 8..9, 11..14, 18..19].into_iter().collect();
+        assert_eq!(free.first_fit(3), Some(2..5));
+        assert_eq!(free.first_fit(1), Some(2..3));
+        assert_eq!(free.first_fit(4), None);
+
+        assert_eq!(
+            free.gaps(0..20).collect::<Vec<_>>(),
+            [0..2, 5..8, 9..11, 14..18, 19..20]
+        );
+        assert_eq!(free.gaps(3..10).collect::<Vec<_>>(), [5..8, 9..10]);
+        assert_eq!(free.gaps(12..13).count(), 0);
+        assert!(RangeSet::<usize>::new().gaps(0..0).next().is_none());
+    }
+
+    #[test]
+    fn maps_ranges() {
+        let mut disk = IntervalMap::new();
+        disk.insert(0..2, 'a');
+        disk.insert(5..8, 'b');
+        disk.insert(6..7, 'c');
+        assert_eq!(
+            disk.iter().collect::<Vec<_>>(),
+            [(0..2, &'a'), (5..6, &'b'), (6..7, &'c'), (7..8, &'b')]
+        );
+        assert_eq!(disk.get(6), Some(&'c'));
+        assert_eq!(disk.get(2), None);
+
+        disk.insert(6..7, 'b');
+        disk.insert(2..3, 'a');
+        assert_eq!(
+            disk.iter().collect::<Vec<_>>(),
+            [(0..3, &'a'), (5..8, &'b')]
+        );
+        assert_eq!(disk.gaps(0..10).collect::<Vec<_>>(), [3..5, 8..10]);
+
+        disk.split_at(6);
+        assert_eq!(disk.len(), 3);
+        disk.remove(1..6);
+        assert_eq!(
+            disk.iter().collect::<Vec<_>>(),
+            [(0..1, &'a'), (6..8, &'b')]
+        );
+        assert!(!disk.is_empty());
+    }
+}
@@ -0,0 +1,258 @@
+/// Reads the answers Advent of Code accepted for a day from its downloaded puzzle description.
+/// aoc-cli includes a line like "Your puzzle answer was `42`." for every solved part.
+///
+/// Accepted answers are recorded in `data/answers.json` together with the hash of the input they belong to,
+/// so a swapped input can be told apart from a broken solution.
+use std::{collections::HashMap, fs, io, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::{config::config, crypto, fingerprint, Day, PuzzleId, Year};
+
+static ANSWER_PREFIX: &str = "Your puzzle answer was";
+
+/// Accepted answers for a day, in part order. Returns `None` if the puzzle has not been downloaded.
+pub fn read_accepted(puzzle: PuzzleId) -> Option<Vec<String>> {
+    crypto::read_to_string(&puzzle.data_path("puzzles", ".md"))
+        .ok()
+        .map(|puzzle| parse_accepted(&puzzle))
+}
+
+/// Records the accepted answers of a puzzle together with the hash of its current input.
+/// Returns `false` if the puzzle has no accepted answers or no input yet.
+pub fn record_verified(puzzle: PuzzleId) -> io::Result<bool> {
+    let (Some(answers), Some(input_hash)) =
+        (read_accepted(puzzle), fingerprint::input_hash(puzzle))
+    else {
+        return Ok(false);
+    };
+
+    if answers.is_empty() {
+        return Ok(false);
+    }
+
+    let mut verified = VerifiedAnswers::read_from_file(puzzle.archive());
+    verified.insert(Verified {
+        day: puzzle.day,
+        answers,
+        input_hash,
+    });
+    verified.store_file(puzzle.archive())?;
+    Ok(true)
+}
+
+/// Records the accepted answers of a puzzle once a solution reproduced one of them on the current input, see
+/// `cargo solve`. Returns `false` if the answer was not accepted, is recorded already or the input changed since.
+pub fn record_reproduced(puzzle: PuzzleId, part: u8, answer: &str) -> io::Result<bool> {
+    let Some(accepted) = read_accepted(puzzle) else {
+        return Ok(false);
+    };
+    if accepted.get(usize::from(part) - 1).map(String::as_str) != Some(answer) {
+        return Ok(false);
+    }
+
+    let verified = VerifiedAnswers::read_from_file(puzzle.archive());
+    let recorded = verified.get(puzzle.day);
+    let input_changed = fingerprint::differs(
+        recorded.map(|v| v.input_hash.as_str()),
+        fingerprint::input_hash(puzzle).as_deref(),
+    );
+    if input_changed || recorded.is_some_and(|v| v.answers == accepted) {
+        return Ok(false);
+    }
+
+    record_verified(puzzle)
+}
+
+fn parse_accepted(puzzle: &str) -> Vec<String> {
+    puzzle
+        .match_indices(ANSWER_PREFIX)
+        .filter_map(|(pos, _)| {
+            let rest = &puzzle[pos + ANSWER_PREFIX.len()..];
+            let (_, rest) = rest.split_once('`')?;
+            let (answer, _) = rest.split_once('`')?;
+            Some(answer.trim().to_string())
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Accepted answers of a day and the hash of the input they were verified against.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Verified {
+    pub day: Day,
+    pub answers: Vec<String>,
+    pub input_hash: String,
+}
+
+/// Verified answers of a year. Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct VerifiedAnswers {
+    pub data: Vec<Verified>,
+}
+
+impl VerifiedAnswers {
+    /// Rehydrate verified answers from a JSON file. If not present, returns an empty set.
+    pub fn read_from_file(archive: Option<Year>) -> Self {
+        fs::read_to_string(config().answers_path(archive))
+            .map_err(|x| x.to_string())
+            .and_then(VerifiedAnswers::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Dehydrate verified answers to a JSON file.
+    pub fn store_file(&self, archive: Option<Year>) -> io::Result<()> {
+        let path = config().answers_path(archive);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    pub fn get(&self, day: Day) -> Option<&Verified> {
+        self.data.iter().find(|v| v.day == day)
+    }
+
+    /// Adds or replaces the record of a day.
+    pub fn insert(&mut self, verified: Verified) {
+        self.data.retain(|v| v.day != verified.day);
+        self.data.push(verified);
+        self.data.sort_unstable_by_key(|v| v.day);
+    }
+}
+
+impl From<&VerifiedAnswers> for JsonValue {
+    fn from(value: &VerifiedAnswers) -> Self {
+        let data = value
+            .data
+            .iter()
+            .map(|verified| {
+                let mut map: HashMap<String, JsonValue> = HashMap::new();
+                map.insert("day".into(), JsonValue::String(verified.day.to_string()));
+                map.insert(
+                    "answers".into(),
+                    JsonValue::Array(
+                        verified
+                            .answers
+                            .iter()
+                            .cloned()
+                            .map(JsonValue::String)
+                            .collect(),
+                    ),
+                );
+                map.insert(
+                    "input_hash".into(),
+                    JsonValue::String(verified.input_hash.clone()),
+                );
+                JsonValue::Object(map)
+            })
+            .collect();
+
+        JsonValue::Object(HashMap::from([("data".into(), JsonValue::Array(data))]))
+    }
+}
+
+impl TryFrom<String> for VerifiedAnswers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let data = json
+            .get::<HashMap<String, JsonValue>>()
+            .and_then(|json| json.get("data"))
+            .and_then(|data| data.get::<Vec<JsonValue>>())
+            .ok_or("expected `json.data` to be an array.")?;
+
+        let data = data
+            .iter()
+            .map(|entry| {
+                let entry = entry
+                    .get::<HashMap<String, JsonValue>>()
+                    .ok_or("Expected entry to be a JSON object.")?;
+
+                let day = entry
+                    .get("day")
+                    .and_then(|v| v.get::<String>())
+                    .and_then(|day| Day::from_str(day).ok())
+                    .ok_or("Expected entry.day to be a Day struct.")?;
+
+                let answers = entry
+                    .get("answers")
+                    .and_then(|v| v.get::<Vec<JsonValue>>())
+                    .and_then(|answers| {
+                        answers
+                            .iter()
+                            .map(|a| a.get::<String>().cloned())
+                            .collect::<Option<Vec<_>>>()
+                    })
+                    .ok_or("Expected entry.answers to be an array of strings.")?;
+
+                let input_hash = entry
+                    .get("input_hash")
+                    .and_then(|v| v.get::<String>())
+                    .cloned()
+                    .ok_or("Expected entry.input_hash to be a string.")?;
+
+                Ok(Verified {
+                    day,
+                    answers,
+                    input_hash,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(VerifiedAnswers { data })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_accepted, Verified, VerifiedAnswers};
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn serializes_verified_answers() {
+        let mut verified = VerifiedAnswers::default();
+        verified.insert(Verified {
+            day: day!(6),
+            answers: vec!["41".into(), "6".into()],
+            input_hash: "abc".into(),
+        });
+        verified.insert(Verified {
+            day: day!(2),
+            answers: vec!["2".into()],
+            input_hash: "def".into(),
+        });
+        verified.insert(Verified {
+            day: day!(6),
+            answers: vec!["41".into()],
+            input_hash: "ghi".into(),
+        });
+
+        assert_eq!(verified.data.len(), 2);
+        assert_eq!(verified.data[0].day, day!(2));
+
+        let json = JsonValue::from(&verified).stringify().unwrap();
+        assert_eq!(VerifiedAnswers::try_from(json).unwrap(), verified);
+        assert_eq!(verified.get(day!(6)).unwrap().input_hash, "ghi");
+    }
+
+    #[test]
+    fn parses_solved_puzzle() {
+        let puzzle = "## \\--- Day 1: Historian Hysteria ---\n\n...\n\nYour puzzle answer was `2430334`.\n\n## \\--- Part Two ---\n\n...\n\nYour puzzle answer was `28786472`.\n\nBoth parts of this puzzle are complete! They provide two gold stars: \\*\\*\n";
+        assert_eq!(parse_accepted(puzzle), vec!["2430334", "28786472"]);
+    }
+
+    #[test]
+    fn parses_unsolved_puzzle() {
+        let puzzle = "## \\--- Day 1: Historian Hysteria ---\n\n...\n";
+        assert!(parse_accepted(puzzle).is_empty());
+    }
+}
@@ -0,0 +1,223 @@
+/// Solutions of several authors for the same day.
+/// Author modules live in `src/bin/NN/<author>.rs` and are registered with `solution!(NN, authors = [...])`.
+use std::path::PathBuf;
+
+use crate::template::{file_names, runner::MAIN_AUTHOR, PuzzleId};
+
+/// Strict and reserved keywords, which can't be module names without `r#`.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Directory with the author modules of a day, e.g. `src/bin/06`.
+pub fn dir(puzzle: PuzzleId) -> PathBuf {
+    puzzle.bin_path().with_extension("")
+}
+
+/// Path of an author module, e.g. `src/bin/06/alice.rs`.
+pub fn module_path(puzzle: PuzzleId, author: &str) -> PathBuf {
+    dir(puzzle).join(format!("{author}.rs"))
+}
+
+/// Authors with a module for a day, sorted by name. They are not necessarily registered yet.
+pub fn list(puzzle: PuzzleId) -> Vec<String> {
+    file_names(&dir(puzzle))
+        .iter()
+        .filter_map(|name| name.strip_suffix(".rs"))
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Author names are module names, so they have to be lowercase identifiers other than keywords.
+/// [`MAIN_AUTHOR`] is reserved: `src/bin/NN/main.rs` would be picked up by cargo as a binary of its own.
+pub fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        && name != "_"
+        && name != MAIN_AUTHOR
+        && !KEYWORDS.contains(&name)
+}
+
+/// Turns a module template into an author module: the day's module defines `DAY` and runs the parts.
+pub fn module_template(template: &str) -> String {
+    let module: Vec<&str> = template
+        .lines()
+        .filter(|line| !line.contains("solution!("))
+        .skip_while(|line| line.trim().is_empty())
+        .collect();
+
+    (module.join("\n") + "\n").replace(", DAY)", ", crate::DAY)")
+}
+
+/// Adds an author to the `solution!` invocation of a day's module, e.g. `solution!(9, generator = generate)`
+/// becomes `solution!(9, authors = [alice], generator = generate)`. Keeps the formatting of the other arguments.
+/// Returns `None` if the invocation has an unsupported form, e.g. `solution!(6, 1)`.
+pub fn register(source: &str, author: &str) -> Option<String> {
+    let start = source.find("solution!(")? + "solution!(".len();
+    let (args, end) = split_args(&source[start..])?;
+    let (day, options) = args.split_first()?;
+
+    let mut registered = None;
+    for (index, option) in options.iter().enumerate() {
+        // a trailing comma leaves a blank argument.
+        if option.trim().is_empty() {
+            continue;
+        }
+        let (key, value) = option.split_once('=')?;
+        match key.trim() {
+            "authors" => registered = Some((index, value)),
+            "part_one" | "part_two" | "generator" => {}
+            _ => return None,
+        }
+    }
+
+    let mut args: Vec<String> = args.iter().map(ToString::to_string).collect();
+    match registered {
+        Some((index, value)) => {
+            let list = value.trim().strip_prefix('[')?.strip_suffix(']')?;
+            let mut authors: Vec<&str> = list
+                .split(',')
+                .map(str::trim)
+                .filter(|a| !a.is_empty())
+                .collect();
+            if !authors.contains(&author) {
+                authors.push(author);
+            }
+
+            let option = &options[index];
+            let (indent, trailing) = surrounding_whitespace(option);
+            args[index + 1] = format!("{indent}authors = [{}]{trailing}", authors.join(", "));
+        }
+        None => {
+            // the macro expects the authors right after the day.
+            let (indent, trailing) = surrounding_whitespace(day);
+            let indent = if indent.is_empty() { " " } else { indent };
+            args[0] = day.trim_end().to_string();
+            args.insert(1, format!("{indent}authors = [{author}]{trailing}"));
+        }
+    }
+
+    Some(format!(
+        "{}{}{}",
+        &source[..start],
+        args.join(","),
+        &source[start + end..]
+    ))
+}
+
+/// Splits the arguments of a macro invocation at top-level commas, keeping their whitespace.
+/// Also returns the offset of the closing parenthesis.
+fn split_args(source: &str) -> Option<(Vec<&str>, usize)> {
+    let (mut depth, mut from, mut args) = (0, 0, vec![]);
+    for (i, c) in source.char_indices() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' if depth == 0 => {
+                args.push(&source[from..i]);
+                return Some((args, i));
+            }
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                args.push(&source[from..i]);
+                from = i + 1;
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn surrounding_whitespace(arg: &str) -> (&str, &str) {
+    let indent = &arg[..arg.len() - arg.trim_start().len()];
+    let trailing = &arg[arg.trim_end().len()..];
+    (indent, trailing)
+}
+
+/// Whether an author is registered in the `solution!` invocation of a day's module.
+pub fn is_registered(source: &str, author: &str) -> bool {
+    register(source, author).is_some_and(|registered| registered == source)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{is_registered, is_valid_name, module_template, register};
+
+    #[test]
+    fn validates_names() {
+        assert!(is_valid_name("alice"));
+        assert!(is_valid_name("bob_2"));
+        assert!(!is_valid_name("Alice"));
+        assert!(!is_valid_name("2bob"));
+        assert!(!is_valid_name("al-ice"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name("_"));
+        assert!(!is_valid_name("main"));
+        assert!(is_valid_name("main_2"));
+        for keyword in [
+            "fn", "mod", "self", "crate", "super", "type", "async", "gen",
+        ] {
+            assert!(!is_valid_name(keyword), "{keyword}");
+        }
+    }
+
+    #[test]
+    fn registers_authors() {
+        let source = "advent_of_code::solution!(6);\n\npub fn part_one() {}\n";
+        let source = register(source, "alice").unwrap();
+        assert_eq!(
+            source,
+            "advent_of_code::solution!(6, authors = [alice]);\n\npub fn part_one() {}\n"
+        );
+
+        let source = register(&source, "bob").unwrap();
+        assert!(source.starts_with("advent_of_code::solution!(6, authors = [alice, bob]);"));
+        assert_eq!(register(&source, "bob").unwrap(), source);
+
+        assert!(is_registered(&source, "alice"));
+        assert!(!is_registered(&source, "carol"));
+        assert!(register("advent_of_code::solution!(6, 1);", "alice").is_none());
+    }
+
+    #[test]
+    fn registers_authors_next_to_variants() {
+        let source = "advent_of_code::solution!(1, generator = generate);";
+        let source = register(source, "alice").unwrap();
+        assert_eq!(
+            source,
+            "advent_of_code::solution!(1, authors = [alice], generator = generate);"
+        );
+        let source = register(&source, "bob").unwrap();
+        assert_eq!(
+            source,
+            "advent_of_code::solution!(1, authors = [alice, bob], generator = generate);"
+        );
+        assert!(is_registered(&source, "bob"));
+
+        let source = "advent_of_code::solution!(\n    9,\n    part_two = [part_two_spans, part_two_windows],\n    generator = generate\n);\n";
+        assert_eq!(
+            register(source, "alice").unwrap(),
+            "advent_of_code::solution!(\n    9,\n    authors = [alice],\n    part_two = [part_two_spans, part_two_windows],\n    generator = generate\n);\n"
+        );
+        assert_eq!(
+            register("advent_of_code::solution!(\n    6\n);", "alice").unwrap(),
+            "advent_of_code::solution!(\n    6,\n    authors = [alice]\n);"
+        );
+        assert!(register("advent_of_code::solution!(6, parts = [1]);", "alice").is_none());
+    }
+
+    #[test]
+    fn builds_module_templates() {
+        let template = "advent_of_code::solution!(%DAY_NUMBER%);\n\npub fn part_one(input: &str) -> Option<u32> {\n    None\n}\n\n#[test]\nfn test() {\n    read_file(\"examples\", DAY);\n}\n";
+        assert_eq!(
+            module_template(template),
+            "pub fn part_one(input: &str) -> Option<u32> {\n    None\n}\n\n#[test]\nfn test() {\n    read_file(\"examples\", crate::DAY);\n}\n"
+        );
+    }
+}
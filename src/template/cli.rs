@@ -0,0 +1,415 @@
+/// Describes the subcommands of the template CLI.
+/// Used to render help texts and usage errors, and to generate shell completions.
+use crate::template::{Year, ANSI_BOLD, ANSI_RESET};
+
+/// Whether a command accepts a day number as positional argument.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DayArg {
+    None,
+    Optional,
+    Required,
+}
+
+pub struct Flag {
+    pub name: &'static str,
+    /// Placeholder for the flag's value, `None` for boolean switches.
+    pub value: Option<&'static str>,
+    pub help: &'static str,
+}
+
+/// A positional argument other than the day, e.g. the shell for `completions`.
+pub struct Positional {
+    pub name: &'static str,
+    pub help: &'static str,
+    /// Possible values, offered by shell completions. Empty if the value is free-form.
+    pub values: &'static [&'static str],
+}
+
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub summary: &'static str,
+    pub day: DayArg,
+    pub flags: &'static [Flag],
+    pub positional: Option<Positional>,
+    pub examples: &'static [&'static str],
+}
+
+const RELEASE: Flag = Flag {
+    name: "--release",
+    value: None,
+    help: "Build and run with optimizations.",
+};
+
+const YEAR: Flag = Flag {
+    name: "--year",
+    value: Some("<year>"),
+    help: "Use the event of another year, e.g. an archived one.",
+};
+
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "scaffold",
+        summary: "Create the solution module, input and example files for a day.",
+        day: DayArg::Required,
+        flags: &[
+            Flag {
+                name: "--download",
+                value: None,
+                help: "Download input and puzzle description via aoc-cli afterwards.",
+            },
+            Flag {
+                name: "--overwrite",
+                value: None,
+                help: "Overwrite an existing solution module.",
+            },
+            Flag {
+                name: "--author",
+                value: Some("<name>"),
+                help: "Create the module of another author in `src/bin/<day>/` and register it.",
+            },
+            YEAR,
+        ],
+        positional: None,
+        examples: &[
+            "cargo scaffold 1",
+            "cargo scaffold 4 --download",
+            "cargo scaffold 4 --author alice",
+        ],
+    },
+    CommandSpec {
+        name: "download",
+        summary: "Download input and puzzle description for a day via aoc-cli.",
+        day: DayArg::Required,
+        flags: &[YEAR],
+        positional: None,
+        examples: &["cargo download 1"],
+    },
+    CommandSpec {
+        name: "read",
+        summary: "Print the puzzle description for a day via aoc-cli.",
+        day: DayArg::Required,
+        flags: &[YEAR],
+        positional: None,
+        examples: &["cargo read 1"],
+    },
+    CommandSpec {
+        name: "solve",
+        summary: "Run the solution for a day against its puzzle input.",
+        day: DayArg::Required,
+        flags: &[
+            RELEASE,
+            Flag {
+                name: "--dhat",
+                value: None,
+                help: "Profile heap allocations with DHAT.",
+            },
+            Flag {
+                name: "--submit",
+                value: Some("<part>"),
+                help: "Submit the answer of part 1 or 2 via aoc-cli.",
+            },
+            Flag {
+                name: "--all-inputs",
+                value: None,
+                help: "Run against every input in `data/inputs/<day>/` and check the expected answers.",
+            },
+            Flag {
+                name: "--author",
+                value: Some("<name>"),
+                help: "Run the solution of another author.",
+            },
+            Flag {
+                name: "--compare",
+                value: None,
+                help: "Bench the solutions of all authors and check that they agree on the answers.",
+            },
+            Flag {
+                name: "--variants",
+                value: None,
+                help: "Bench the registered variants of each part and check that they agree on the answers.",
+            },
+            YEAR,
+        ],
+        positional: None,
+        examples: &[
+            "cargo solve 1",
+            "cargo solve 1 --submit 2",
+            "cargo solve 6 --all-inputs",
+            "cargo solve 6 --author alice",
+            "cargo solve 6 --compare --release",
+            "cargo solve 9 --variants --release",
+            "cargo solve 5 --year 2023",
+        ],
+    },
+    CommandSpec {
+        name: "all",
+        summary: "Run the solutions for all days.",
+        day: DayArg::None,
+        flags: &[RELEASE, YEAR],
+        positional: None,
+        examples: &["cargo all", "cargo all --release", "cargo all --year 2023"],
+    },
+    CommandSpec {
+        name: "time",
+        summary: "Benchmark solutions and optionally store timings in the readme.",
+        day: DayArg::Optional,
+        flags: &[
+            Flag {
+                name: "--all",
+                value: None,
+                help: "Bench all days, including days that already have stored timings.",
+            },
+            Flag {
+                name: "--store",
+                value: None,
+                help: "Store timings and update the readme benchmark table.",
+            },
+            Flag {
+                name: "--host",
+                value: Some("<name>"),
+                help: "Read and store timings in a separate per-host profile.",
+            },
+            YEAR,
+        ],
+        positional: None,
+        examples: &[
+            "cargo time",
+            "cargo time 8 --store",
+            "cargo time --all --store",
+        ],
+    },
+    CommandSpec {
+        name: "scale",
+        summary: "Time a solution on generated inputs of growing size and estimate its complexity.",
+        day: DayArg::Required,
+        flags: &[YEAR],
+        positional: None,
+        examples: &["cargo scale 9", "cargo scale 5 --year 2023"],
+    },
+    CommandSpec {
+        name: "status",
+        summary: "Show files, answers, stars and benchmarks for every day.",
+        day: DayArg::None,
+        flags: &[Flag {
+            name: "--no-run",
+            value: None,
+            help: "Skip running solutions, only check files, stars and timings.",
+        }],
+        positional: None,
+        examples: &["cargo status", "cargo status --no-run"],
+    },
+    CommandSpec {
+        name: "today",
+        summary: "Scaffold, download and read the puzzle of the current day.",
+        day: DayArg::None,
+        flags: &[Flag {
+            name: "--wait",
+            value: None,
+            help: "Count down to the next unlock, then scaffold, download and read it.",
+        }],
+        positional: None,
+        examples: &["cargo today", "cargo today --wait"],
+    },
+    CommandSpec {
+        name: "new-year",
+        summary: "Archive the current year and start working on another one.",
+        day: DayArg::None,
+        flags: &[],
+        positional: Some(Positional {
+            name: "<year>",
+            help: "Year of the event to work on next.",
+            values: &[],
+        }),
+        examples: &["cargo new-year 2025"],
+    },
+    CommandSpec {
+        name: "inputs",
+        summary: "Encrypt inputs and puzzles for committing, or decrypt them after a checkout.",
+        day: DayArg::None,
+        flags: &[],
+        positional: Some(Positional {
+            name: "<action>",
+            help: "Whether to write encrypted `.enc` files, restore plain files from them or check that no plain files are staged.",
+            values: &["encrypt", "decrypt", "check"],
+        }),
+        examples: &[
+            "cargo inputs encrypt",
+            "cargo inputs decrypt",
+            "cargo inputs check",
+        ],
+    },
+    CommandSpec {
+        name: "completions",
+        summary: "Print a shell completion script for the cargo aliases.",
+        day: DayArg::None,
+        flags: &[],
+        positional: Some(Positional {
+            name: "<shell>",
+            help: "Shell to generate the script for.",
+            values: &["bash", "zsh", "fish"],
+        }),
+        examples: &[
+            "cargo completions bash > ~/.local/share/bash-completion/completions/cargo-aoc",
+            "cargo completions fish > ~/.config/fish/completions/cargo-aoc.fish",
+        ],
+    },
+];
+
+/// Looks up the spec of a subcommand by name.
+pub fn find(name: &str) -> Option<&'static CommandSpec> {
+    COMMANDS.iter().find(|c| c.name == name)
+}
+
+impl CommandSpec {
+    /// One-line usage, e.g. `cargo solve <day> [--release] [--submit <part>]`.
+    pub fn usage(&self) -> String {
+        let mut parts = vec![format!("cargo {}", self.name)];
+
+        match self.day {
+            DayArg::None => {}
+            DayArg::Optional => parts.push("[day]".into()),
+            DayArg::Required => parts.push("<day>".into()),
+        }
+
+        if let Some(positional) = &self.positional {
+            parts.push(positional.name.into());
+        }
+
+        for flag in self.flags {
+            match flag.value {
+                Some(value) => parts.push(format!("[{} {value}]", flag.name)),
+                None => parts.push(format!("[{}]", flag.name)),
+            }
+        }
+
+        parts.join(" ")
+    }
+
+    /// Full help text shown for `cargo <command> --help`.
+    pub fn help(&self) -> String {
+        let mut lines = vec![
+            self.summary.to_string(),
+            String::new(),
+            format!("{ANSI_BOLD}Usage:{ANSI_RESET} {}", self.usage()),
+        ];
+
+        if self.day != DayArg::None || self.positional.is_some() || !self.flags.is_empty() {
+            lines.push(String::new());
+            lines.push(format!("{ANSI_BOLD}Arguments:{ANSI_RESET}"));
+        }
+
+        if self.day != DayArg::None {
+            lines.push(format!(
+                "  {:<18} Day number between 1 and {}.",
+                "day",
+                Year::current().days()
+            ));
+        }
+
+        if let Some(positional) = &self.positional {
+            let mut help = positional.help.to_string();
+            if !positional.values.is_empty() {
+                help.push_str(&format!(" One of: {}.", positional.values.join(", ")));
+            }
+            lines.push(format!("  {:<18} {help}", positional.name));
+        }
+
+        for flag in self.flags {
+            let name = match flag.value {
+                Some(value) => format!("{} {value}", flag.name),
+                None => flag.name.to_string(),
+            };
+            lines.push(format!("  {name:<18} {}", flag.help));
+        }
+        lines.push(format!("  {:<18} Print this help.", "-h, --help"));
+        // the commands with a `[defaults]` entry in `aoc.toml`, see `Config::default_flags`.
+        if matches!(self.name, "solve" | "all" | "time") {
+            lines.push(format!(
+                "  {:<18} Turn off a default flag from `aoc.toml`, e.g. `--no-release`.",
+                "--no-<flag>"
+            ));
+        }
+
+        lines.push(String::new());
+        lines.push(format!("{ANSI_BOLD}Examples:{ANSI_RESET}"));
+        for example in self.examples {
+            lines.push(format!("  {example}"));
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Overview of all commands, shown for `--help` and when no command is passed.
+pub fn overview() -> String {
+    let mut lines = vec![
+        "🎄 Advent of Code template CLI".to_string(),
+        String::new(),
+        format!("{ANSI_BOLD}Usage:{ANSI_RESET} cargo <command> [args]"),
+        String::new(),
+        format!("{ANSI_BOLD}Commands:{ANSI_RESET}"),
+    ];
+
+    for command in COMMANDS {
+        lines.push(format!("  {:<12} {}", command.name, command.summary));
+    }
+
+    lines.push(String::new());
+    lines.push("Run `cargo <command> --help` for details on a command.".into());
+    lines.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find, COMMANDS};
+
+    #[test]
+    fn builds_usage() {
+        assert_eq!(
+            find("solve").unwrap().usage(),
+            "cargo solve <day> [--release] [--dhat] [--submit <part>] [--all-inputs] [--author <name>] [--compare] [--variants] [--year <year>]"
+        );
+        assert_eq!(
+            find("time").unwrap().usage(),
+            "cargo time [day] [--all] [--store] [--host <name>] [--year <year>]"
+        );
+        assert_eq!(
+            find("completions").unwrap().usage(),
+            "cargo completions <shell>"
+        );
+    }
+
+    #[test]
+    fn every_command_has_an_alias() {
+        // usage and help print `cargo <command>`, which only works with an alias.
+        let config = include_str!("../../.cargo/config.toml");
+        for command in COMMANDS {
+            let alias = format!(
+                "{} = \"run --quiet --release -- {}\"",
+                command.name, command.name
+            );
+            assert!(
+                config.contains(&alias),
+                "missing alias for {}",
+                command.name
+            );
+        }
+    }
+
+    #[test]
+    fn help_lists_flags_and_examples() {
+        let help = find("scaffold").unwrap().help();
+        assert!(help.contains("--download"));
+        assert!(help.contains("--overwrite"));
+        assert!(help.contains("cargo scaffold 4 --download"));
+        assert!(!help.contains("--no-<flag>"));
+        assert!(find("time").unwrap().help().contains("--no-<flag>"));
+    }
+
+    #[test]
+    fn every_command_has_an_example() {
+        assert!(COMMANDS.iter().all(|c| !c.examples.is_empty()));
+    }
+}
@@ -0,0 +1,280 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::template::{
+    all_days,
+    cli::{CommandSpec, DayArg, COMMANDS},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            other => Err(format!(
+                "unsupported shell `{other}`, expecting one of bash, zsh, fish"
+            )),
+        }
+    }
+}
+
+impl Display for Shell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Shell::Bash => "bash",
+            Shell::Zsh => "zsh",
+            Shell::Fish => "fish",
+        })
+    }
+}
+
+pub fn handle(shell: Shell) {
+    println!("{}", generate(shell));
+}
+
+/// Builds a completion script for the template's cargo aliases (`cargo solve`, `cargo time`, ...).
+/// The scripts extend the regular cargo completions and fall back to them for other subcommands.
+pub fn generate(shell: Shell) -> String {
+    match shell {
+        Shell::Bash => bash(),
+        Shell::Zsh => zsh(),
+        Shell::Fish => fish(),
+    }
+}
+
+fn days() -> Vec<String> {
+    all_days().map(|d| d.into_inner().to_string()).collect()
+}
+
+/// Candidates for the positional argument, i.e. day numbers or a fixed set of values.
+fn positional_values(command: &CommandSpec) -> Vec<String> {
+    match (command.day, &command.positional) {
+        (DayArg::None, Some(positional)) => {
+            positional.values.iter().map(ToString::to_string).collect()
+        }
+        (DayArg::None, None) => vec![],
+        _ => days(),
+    }
+}
+
+fn flag_names(command: &CommandSpec) -> Vec<&'static str> {
+    command
+        .flags
+        .iter()
+        .map(|f| f.name)
+        .chain(["--help"])
+        .collect()
+}
+
+fn bash() -> String {
+    let mut cases = vec![];
+
+    for command in COMMANDS {
+        let value_flags: Vec<_> = command
+            .flags
+            .iter()
+            .filter(|f| f.value.is_some())
+            .map(|f| f.name)
+            .collect();
+
+        let mut case = format!("        {})\n", command.name);
+        if !value_flags.is_empty() {
+            case.push_str(&format!(
+                "            case \"$prev\" in {}) return 0 ;; esac\n",
+                value_flags.join("|")
+            ));
+        }
+        case.push_str(&format!(
+            "            COMPREPLY=($(compgen -W \"{} {}\" -- \"$cur\"))\n            return 0\n            ;;",
+            positional_values(command).join(" "),
+            flag_names(command).join(" ")
+        ));
+        cases.push(case);
+    }
+
+    let names: Vec<_> = COMMANDS.iter().map(|c| c.name).collect();
+
+    format!(
+        r#"# bash completion for the advent-of-code cargo aliases.
+# make sure the regular cargo completion is loaded, so we can fall back to it.
+if ! declare -F _cargo >/dev/null; then
+    _completion_loader cargo 2>/dev/null
+fi
+
+_cargo_aoc() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    local prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+
+    if [[ $COMP_CWORD -eq 1 ]]; then
+        COMPREPLY=($(compgen -W "{names}" -- "$cur"))
+        if declare -F _cargo >/dev/null; then
+            local aoc=("${{COMPREPLY[@]}}")
+            _cargo "$@"
+            COMPREPLY+=("${{aoc[@]}}")
+        fi
+        return 0
+    fi
+
+    case "${{COMP_WORDS[1]}}" in
+{cases}
+    esac
+
+    if declare -F _cargo >/dev/null; then
+        _cargo "$@"
+    fi
+}}
+
+complete -F _cargo_aoc cargo
+"#,
+        names = names.join(" "),
+        cases = cases.join("\n")
+    )
+}
+
+fn zsh() -> String {
+    let mut cases = vec![];
+
+    for command in COMMANDS {
+        let mut specs: Vec<String> = command
+            .flags
+            .iter()
+            .map(|f| {
+                let help = f.help.replace('\'', "").replace(['[', ']'], "");
+                match f.value {
+                    Some(value) => {
+                        format!("'{}[{help}]:{}:'", f.name, value.trim_matches(['<', '>']))
+                    }
+                    None => format!("'{}[{help}]'", f.name),
+                }
+            })
+            .collect();
+        specs.push("'(-h --help)'{-h,--help}'[Print help]'".into());
+
+        let values = positional_values(command);
+        if !values.is_empty() {
+            specs.push(format!("'1:value:({})'", values.join(" ")));
+        }
+
+        cases.push(format!(
+            "        {})\n            _arguments {}\n            ;;",
+            command.name,
+            specs.join(" \\\n                ")
+        ));
+    }
+
+    let commands: Vec<_> = COMMANDS
+        .iter()
+        .map(|c| format!("'{}:{}'", c.name, c.summary.replace('\'', "")))
+        .collect();
+
+    format!(
+        r#"#compdef cargo
+# zsh completion for the advent-of-code cargo aliases.
+# load this after the regular cargo completion, e.g. by sourcing it from `.zshrc` after `compinit`.
+
+_cargo_aoc() {{
+    local -a aoc_commands
+    aoc_commands=({commands})
+
+    if (( CURRENT == 2 )); then
+        _describe -t aoc-commands 'advent of code command' aoc_commands
+        (( $+functions[_cargo] )) && _cargo
+        return
+    fi
+
+    case "$words[2]" in
+{cases}
+        *)
+            (( $+functions[_cargo] )) && _cargo
+            ;;
+    esac
+}}
+
+compdef _cargo_aoc cargo
+"#,
+        commands = commands.join(" "),
+        cases = cases.join("\n")
+    )
+}
+
+fn fish() -> String {
+    let mut lines = vec![
+        "# fish completion for the advent-of-code cargo aliases.".to_string(),
+        "# fish merges these with the regular cargo completions.".to_string(),
+    ];
+
+    for command in COMMANDS {
+        lines.push(format!(
+            "complete -c cargo -n '__fish_use_subcommand' -f -a '{}' -d '{}'",
+            command.name,
+            command.summary.replace('\'', "\\'")
+        ));
+
+        let condition = format!("__fish_seen_subcommand_from {}", command.name);
+
+        let values = positional_values(command);
+        if !values.is_empty() {
+            lines.push(format!(
+                "complete -c cargo -n '{condition}' -f -a '{}'",
+                values.join(" ")
+            ));
+        }
+
+        for flag in command.flags {
+            let requires_value = if flag.value.is_some() { " -r" } else { "" };
+            lines.push(format!(
+                "complete -c cargo -n '{condition}' -l {}{requires_value} -d '{}'",
+                flag.name.trim_start_matches("--"),
+                flag.help.replace('\'', "\\'")
+            ));
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{generate, Shell};
+
+    #[test]
+    fn parses_shell_names() {
+        assert_eq!("zsh".parse::<Shell>(), Ok(Shell::Zsh));
+        assert!("powershell".parse::<Shell>().is_err());
+    }
+
+    #[test]
+    fn bash_completes_days_and_flags() {
+        let script = generate(Shell::Bash);
+        assert!(script.contains("complete -F _cargo_aoc cargo"));
+        assert!(script.contains("1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 --release --dhat --submit --all-inputs --author --compare --variants --year --help"));
+        assert!(script.contains("bash zsh fish --help"));
+    }
+
+    #[test]
+    fn zsh_completes_days_and_flags() {
+        let script = generate(Shell::Zsh);
+        assert!(script.starts_with("#compdef cargo"));
+        assert!(script.contains("'--submit[Submit the answer of part 1 or 2 via aoc-cli.]:part:'"));
+        assert!(script.contains("'1:value:(1 2 3"));
+    }
+
+    #[test]
+    fn fish_completes_days_and_flags() {
+        let script = generate(Shell::Fish);
+        assert!(script.contains(
+            "complete -c cargo -n '__fish_seen_subcommand_from time' -l host -r -d 'Read and store timings in a separate per-host profile.'"
+        ));
+        assert!(script
+            .contains("complete -c cargo -n '__fish_seen_subcommand_from solve' -f -a '1 2 3"));
+    }
+}
@@ -0,0 +1,262 @@
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    process::{self, Command},
+    str::FromStr,
+};
+
+use crate::template::{
+    config::config,
+    crypto::{self, CryptoError, Key, EXTENSION},
+    dir_names, file_names,
+};
+
+/// Data folders that must not be published in plain text.
+const PRIVATE_FOLDERS: [&str; 2] = ["inputs", "puzzles"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Encrypt,
+    Decrypt,
+    /// Refuses plain files among the staged changes, see `.githooks/pre-commit`.
+    Check,
+}
+
+impl FromStr for Action {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "encrypt" => Ok(Action::Encrypt),
+            "decrypt" => Ok(Action::Decrypt),
+            "check" => Ok(Action::Check),
+            other => Err(format!(
+                "unknown action `{other}`, expecting encrypt, decrypt or check"
+            )),
+        }
+    }
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Action::Encrypt => "encrypt",
+            Action::Decrypt => "decrypt",
+            Action::Check => "check",
+        })
+    }
+}
+
+pub fn handle(action: Action) {
+    if action == Action::Check {
+        check_staged();
+        return;
+    }
+
+    let key = match (Key::load(), action) {
+        (Ok(key), _) => key,
+        (Err(CryptoError::MissingKey), Action::Encrypt) => create_key_file(),
+        (Err(e), _) => {
+            eprintln!("Failed to load key: {e}");
+            process::exit(1);
+        }
+    };
+
+    let mut failed = false;
+    let mut written = 0;
+
+    for dir in private_dirs() {
+        for name in file_names(&dir) {
+            let path = dir.join(&name);
+            let result = match action {
+                // expected answers of named inputs are not private.
+                Action::Encrypt if name.ends_with(".toml") => Ok(false),
+                Action::Encrypt if !name.ends_with(&format!(".{EXTENSION}")) => {
+                    encrypt_file(&key, &path, &name)
+                }
+                Action::Decrypt => match name.strip_suffix(&format!(".{EXTENSION}")) {
+                    Some(plain_name) => {
+                        decrypt_file(&key, &path, &dir.join(plain_name), plain_name)
+                    }
+                    None => Ok(false),
+                },
+                Action::Encrypt | Action::Check => Ok(false),
+            };
+
+            match result {
+                Ok(true) => written += 1,
+                Ok(false) => {}
+                Err(e) => {
+                    eprintln!("Failed to {action} \"{}\": {e}", path.display());
+                    failed = true;
+                }
+            }
+        }
+    }
+
+    match action {
+        Action::Encrypt => println!("🔒 Encrypted {written} file(s)."),
+        Action::Decrypt => println!("🔓 Decrypted {written} file(s)."),
+        Action::Check => {}
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// Writes the encrypted file for `path`. Returns `false` if an up-to-date encrypted file exists.
+/// Unchanged files are skipped since every encryption produces a different blob.
+fn encrypt_file(key: &Key, path: &Path, name: &str) -> Result<bool, CryptoError> {
+    let plaintext = fs::read(path)?;
+    let encrypted_path = crypto::encrypted_path(path);
+
+    let unchanged = fs::read(&encrypted_path)
+        .ok()
+        .and_then(|blob| crypto::decrypt(key, name, &blob).ok())
+        .is_some_and(|existing| existing == plaintext);
+
+    if unchanged {
+        return Ok(false);
+    }
+
+    fs::write(&encrypted_path, crypto::encrypt(key, name, &plaintext))?;
+    println!("Encrypted \"{}\"", path.display());
+    Ok(true)
+}
+
+/// Writes the plain file for an encrypted file. Existing plain files are never overwritten.
+fn decrypt_file(
+    key: &Key,
+    encrypted_path: &Path,
+    path: &Path,
+    name: &str,
+) -> Result<bool, CryptoError> {
+    let plaintext = crypto::decrypt(key, name, &fs::read(encrypted_path)?)?;
+
+    match fs::read(path) {
+        Ok(existing) if existing == plaintext => Ok(false),
+        Ok(_) => {
+            eprintln!(
+                "Skipping \"{}\", it differs from the encrypted file. Run `cargo inputs encrypt` to update it.",
+                path.display()
+            );
+            Ok(false)
+        }
+        Err(_) => {
+            fs::write(path, plaintext)?;
+            println!("Decrypted \"{}\"", path.display());
+            Ok(true)
+        }
+    }
+}
+
+/// Exits with an error if plain inputs or puzzles are staged, e.g. after `git add -f`.
+/// Reads the data directory from the config, so the pre-commit hook works with a custom `paths.data`.
+fn check_staged() {
+    let output = Command::new("git")
+        .args(["diff", "--cached", "--name-only", "--diff-filter=ACMR"])
+        .output();
+    let staged = match output {
+        Ok(output) if output.status.success() => {
+            String::from_utf8_lossy(&output.stdout).to_string()
+        }
+        Ok(output) => {
+            eprintln!(
+                "Failed to list staged files: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Failed to call git: {e}");
+            process::exit(1);
+        }
+    };
+
+    let dirs = private_dirs();
+    let plaintext: Vec<&str> = staged
+        .lines()
+        .filter(|path| is_plaintext(Path::new(path), &dirs))
+        .collect();
+
+    if !plaintext.is_empty() {
+        eprintln!("Refusing to commit plain puzzle inputs:");
+        for path in plaintext {
+            eprintln!("    {path}");
+        }
+        eprintln!("Run `cargo inputs encrypt` and commit the .enc files instead.");
+        process::exit(1);
+    }
+}
+
+/// Whether a file in one of the private `dirs` is not encrypted. Hidden files like `.keep` and the expected
+/// answers of named inputs are not private.
+fn is_plaintext(path: &Path, dirs: &[PathBuf]) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+
+    path.parent()
+        .is_some_and(|dir| dirs.iter().any(|d| d == dir))
+        && !name.starts_with('.')
+        && !name.ends_with(".toml")
+        && !name.ends_with(&format!(".{EXTENSION}"))
+}
+
+fn create_key_file() -> Key {
+    let path = &config().paths.key;
+    let key = Key::generate();
+
+    if let Err(e) = fs::write(path, key.to_hex() + "\n") {
+        eprintln!("Failed to write key file \"{path}\": {e}");
+        process::exit(1);
+    }
+
+    println!("🔑 Created a new key in \"{path}\". Keep it private and out of version control.");
+    println!(
+        "   To decrypt inputs elsewhere, e.g. in CI, copy the file or set {}.",
+        crypto::KEY_ENV_VAR
+    );
+    key
+}
+
+/// Input and puzzle directories of the working set and all archived years,
+/// including the directories of named inputs like `data/inputs/06/`.
+fn private_dirs() -> Vec<PathBuf> {
+    let config = config();
+    let years = [None]
+        .into_iter()
+        .chain(config.archived_years().into_iter().map(Some));
+
+    years
+        .flat_map(|year| {
+            PRIVATE_FOLDERS
+                .iter()
+                .map(move |folder| config.data_path(year, folder, ""))
+        })
+        .flat_map(|dir| {
+            let named = dir_names(&dir).into_iter().map(|name| dir.join(name));
+            [dir.clone()].into_iter().chain(named).collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::is_plaintext;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn finds_plaintext() {
+        let dirs = [PathBuf::from("aoc/inputs"), PathBuf::from("aoc/inputs/06")];
+        assert!(is_plaintext(Path::new("aoc/inputs/01.txt"), &dirs));
+        assert!(is_plaintext(Path::new("aoc/inputs/06/alice.txt"), &dirs));
+        assert!(!is_plaintext(Path::new("aoc/inputs/01.txt.enc"), &dirs));
+        assert!(!is_plaintext(Path::new("aoc/inputs/.keep"), &dirs));
+        assert!(!is_plaintext(Path::new("aoc/inputs/06/alice.toml"), &dirs));
+        assert!(!is_plaintext(Path::new("aoc/examples/01.txt"), &dirs));
+        assert!(!is_plaintext(Path::new("data/inputs/01.txt"), &dirs));
+    }
+}
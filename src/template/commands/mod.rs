@@ -1,6 +1,12 @@
 pub mod all;
+pub mod completions;
 pub mod download;
+pub mod inputs;
+pub mod new_year;
 pub mod read;
 pub mod scaffold;
+pub mod scale;
 pub mod solve;
+pub mod status;
 pub mod time;
+pub mod today;
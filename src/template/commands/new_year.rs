@@ -0,0 +1,275 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::template::{
+    config::{self, config, Config},
+    dir_names, file_names, readme_benchmarks,
+    timings::Timings,
+    Year,
+};
+
+/// Data folders that are archived alongside the solutions.
+const DATA_FOLDERS: [&str; 3] = ["inputs", "examples", "puzzles"];
+
+pub fn handle(year: Year) {
+    let current = Year::current();
+
+    if year == current {
+        eprintln!("{year} is already the current year.");
+        process::exit(1);
+    }
+
+    let config = config();
+
+    // plan every move up front, so we do not leave a half-archived year behind on conflicts.
+    let archive = archive_moves(config, current);
+    if let Some((_, to)) = archive.iter().find(|(_, to)| to.exists()) {
+        eprintln!(
+            "Cannot archive {current}: \"{}\" already exists.",
+            to.display()
+        );
+        process::exit(1);
+    }
+
+    let restore = restore_moves(config, year);
+    if let Some(to) = blocked_restore(&archive, &restore) {
+        eprintln!(
+            "Cannot restore {year}: \"{}\" already exists.",
+            to.display()
+        );
+        process::exit(1);
+    }
+
+    for (from, to) in archive.iter().chain(&restore) {
+        if let Err(e) = move_path(from, to) {
+            eprintln!(
+                "Failed to move \"{}\" to \"{}\": {e}",
+                from.display(),
+                to.display()
+            );
+            process::exit(1);
+        }
+    }
+
+    remove_empty_dirs(&config.data_dir(Some(year)));
+
+    if let Err(e) = config::store_year(year) {
+        eprintln!("Failed to update the year in aoc.toml: {e}");
+        process::exit(1);
+    }
+
+    let mut tables = vec![(None, Timings::read_from_file(None, None))];
+    tables.extend(
+        archived_years(config, year)
+            .into_iter()
+            .map(|y| (Some(y), Timings::read_from_file(Some(y), None))),
+    );
+
+    if let Err(e) = readme_benchmarks::update(year, tables) {
+        eprintln!("Failed to update readme with benchmarks: {e:?}");
+    }
+
+    println!(
+        "📦 Archived {} files of {current}, run them with `--year {current}`.",
+        archive.len()
+    );
+    if !restore.is_empty() {
+        println!("📦 Restored {} archived files of {year}.", restore.len());
+    }
+    println!("🎄 Happy Advent of Code {year}!");
+
+    if env::var("AOC_YEAR").is_ok() {
+        eprintln!("Note: the AOC_YEAR environment variable overrides the year set in aoc.toml.");
+    }
+}
+
+/// Moves of the working set into the archive of `year`.
+fn archive_moves(config: &Config, year: Year) -> Vec<(PathBuf, PathBuf)> {
+    let mut moves = vec![];
+
+    for name in file_names(Path::new(&config.paths.bin)) {
+        if let Some(day) = working_set_bin(&name) {
+            moves.push((
+                config.bin_path(day),
+                config.bin_path(&format!("{year}-{day}")),
+            ));
+        }
+    }
+
+    // modules of other authors, e.g. `src/bin/06/`.
+    for name in dir_names(Path::new(&config.paths.bin)) {
+        if working_set_bin(&format!("{name}.rs")).is_some() {
+            moves.push((
+                Path::new(&config.paths.bin).join(&name),
+                Path::new(&config.paths.bin).join(format!("{year}-{name}")),
+            ));
+        }
+    }
+
+    for folder in DATA_FOLDERS {
+        let dir = config.data_path(None, folder, "");
+        // subdirectories hold named inputs, e.g. `data/inputs/06/`.
+        for name in file_names(&dir).into_iter().chain(dir_names(&dir)) {
+            moves.push((
+                config.data_path(None, folder, &name),
+                config.data_path(Some(year), folder, &name),
+            ));
+        }
+    }
+
+    moves.extend(timings_moves(config, None, Some(year)));
+    moves
+}
+
+/// Moves of an archived year back into the working set. Empty if the year was not archived.
+fn restore_moves(config: &Config, year: Year) -> Vec<(PathBuf, PathBuf)> {
+    let mut moves = vec![];
+    let prefix = format!("{year}-");
+
+    for name in file_names(Path::new(&config.paths.bin)) {
+        let Some(day) = name
+            .strip_prefix(&prefix)
+            .and_then(|name| working_set_bin(name))
+        else {
+            continue;
+        };
+        moves.push((
+            config.bin_path(&format!("{year}-{day}")),
+            config.bin_path(day),
+        ));
+    }
+
+    for name in dir_names(Path::new(&config.paths.bin)) {
+        let Some(day) = name
+            .strip_prefix(&prefix)
+            .filter(|day| working_set_bin(&format!("{day}.rs")).is_some())
+        else {
+            continue;
+        };
+        moves.push((
+            Path::new(&config.paths.bin).join(&name),
+            Path::new(&config.paths.bin).join(day),
+        ));
+    }
+
+    for folder in DATA_FOLDERS {
+        let dir = config.data_path(Some(year), folder, "");
+        // subdirectories hold named inputs, e.g. `data/inputs/06/`.
+        for name in file_names(&dir).into_iter().chain(dir_names(&dir)) {
+            moves.push((
+                config.data_path(Some(year), folder, &name),
+                config.data_path(None, folder, &name),
+            ));
+        }
+    }
+
+    moves.extend(timings_moves(config, Some(year), None));
+    moves
+}
+
+/// The first restore target that is in the way. Targets of the working set are free once it is archived.
+fn blocked_restore<'a>(
+    archive: &[(PathBuf, PathBuf)],
+    restore: &'a [(PathBuf, PathBuf)],
+) -> Option<&'a PathBuf> {
+    restore
+        .iter()
+        .map(|(_, to)| to)
+        .find(|&to| to.exists() && !archive.iter().any(|(from, _)| from == to))
+}
+
+/// The stored timings, the directory with per-host profiles and the verified answers.
+fn timings_moves(config: &Config, from: Option<Year>, to: Option<Year>) -> Vec<(PathBuf, PathBuf)> {
+    let profiles = |archive| {
+        config
+            .timings_path(archive, Some("_"))
+            .parent()
+            .map(Path::to_path_buf)
+    };
+
+    [
+        Some((
+            config.timings_path(from, None),
+            config.timings_path(to, None),
+        )),
+        profiles(from).zip(profiles(to)),
+        Some((config.answers_path(from), config.answers_path(to))),
+    ]
+    .into_iter()
+    .flatten()
+    .filter(|(from, _)| from.exists())
+    .collect()
+}
+
+/// Returns the day of a solution module of the working set, e.g. `08` for `08.rs`.
+fn working_set_bin(file_name: &str) -> Option<&str> {
+    let day = file_name.strip_suffix(".rs")?;
+    (day.len() == 2 && day.bytes().all(|b| b.is_ascii_digit())).then_some(day)
+}
+
+fn move_path(from: &Path, to: &Path) -> io::Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(from, to)
+}
+
+/// Removes the directory of a restored archive, keeping it if it still contains files.
+fn remove_empty_dirs(dir: &Path) {
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            if entry.path().is_dir() {
+                remove_empty_dirs(&entry.path());
+            }
+        }
+    }
+    let _ = fs::remove_dir(dir);
+}
+
+/// Archived years after the rollover. `config().archived_years()` can not be used as-is,
+/// since it is not aware that `working_set` is no longer an archive.
+fn archived_years(config: &Config, working_set: Year) -> Vec<Year> {
+    config
+        .archived_years()
+        .into_iter()
+        .filter(|&year| year != working_set)
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{blocked_restore, working_set_bin};
+    use std::path::PathBuf;
+
+    #[test]
+    fn detects_working_set_bins() {
+        assert_eq!(working_set_bin("08.rs"), Some("08"));
+        assert_eq!(working_set_bin("2023-08.rs"), None);
+        assert_eq!(working_set_bin("main.rs"), None);
+    }
+
+    #[test]
+    fn detects_blocked_restores() {
+        // tests run in the crate root, where `Cargo.toml` exists.
+        let restore = [
+            (PathBuf::from("2023-missing"), PathBuf::from("missing")),
+            (
+                PathBuf::from("2023-Cargo.toml"),
+                PathBuf::from("Cargo.toml"),
+            ),
+        ];
+        assert_eq!(
+            blocked_restore(&[], &restore),
+            Some(&PathBuf::from("Cargo.toml"))
+        );
+
+        let archive = [(
+            PathBuf::from("Cargo.toml"),
+            PathBuf::from("2024-Cargo.toml"),
+        )];
+        assert_eq!(blocked_restore(&archive, &restore), None);
+    }
+}
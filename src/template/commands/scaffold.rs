@@ -1,10 +1,10 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{authors, config::config, crypto, PuzzleId};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
@@ -27,10 +27,70 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Returns the module template configured in `aoc.toml`, or the built-in one.
+fn module_template() -> String {
+    let Some(path) = &config().scaffold.template else {
+        return MODULE_TEMPLATE.into();
+    };
+
+    match fs::read_to_string(path) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("Failed to read module template \"{path}\": {e}");
+            process::exit(1);
+        }
+    }
+}
+
+pub fn handle(puzzle: PuzzleId, overwrite: bool, author: Option<&str>) {
+    match author {
+        None => scaffold_day(puzzle, overwrite),
+        Some(author) => {
+            // the day's module registers the author, so it has to exist first.
+            if !puzzle.bin_path().exists() {
+                scaffold_day(puzzle, false);
+            }
+            scaffold_author(puzzle, author, overwrite);
+        }
+    }
+
+    let year = match puzzle.archive() {
+        Some(year) => format!(" --year {year}"),
+        None => String::new(),
+    };
+    let author = match author {
+        Some(author) => format!(" --author {author}"),
+        None => String::new(),
+    };
+
+    println!("---");
+    println!(
+        "🎄 Type `cargo solve {}{year}{author}` to run your solution.",
+        puzzle.day
+    );
+}
+
+fn scaffold_day(puzzle: PuzzleId, overwrite: bool) {
+    let input_path = puzzle.data_path("inputs", ".txt");
+    let example_path = puzzle.data_path("examples", ".txt");
+
+    // archived years keep their data in a separate directory that might not exist yet.
+    for dir in [&input_path, &example_path]
+        .iter()
+        .filter_map(|p| p.parent())
+    {
+        if let Err(e) = fs::create_dir_all(dir) {
+            eprintln!("Failed to create directory \"{}\": {e}", dir.display());
+            process::exit(1);
+        }
+    }
+
+    // an empty plain file would shadow an input that was committed in encrypted form.
+    let has_encrypted_input = crypto::encrypted_path(&input_path).exists();
+
+    let input_path = input_path.display().to_string();
+    let example_path = example_path.display().to_string();
+    let module_path = puzzle.bin_path().display().to_string();
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -41,8 +101,8 @@ pub fn handle(day: Day, overwrite: bool) {
     };
 
     match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+        module_template()
+            .replace("%DAY_NUMBER%", &puzzle.day.into_inner().to_string())
             .as_bytes(),
     ) {
         Ok(()) => {
@@ -54,13 +114,17 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    if has_encrypted_input {
+        println!("Found encrypted input file \"{}.enc\"", &input_path);
+    } else {
+        match create_file(&input_path) {
+            Ok(_) => {
+                println!("Created empty input file \"{}\"", &input_path);
+            }
+            Err(e) => {
+                eprintln!("Failed to create input file: {e}");
+                process::exit(1);
+            }
         }
     }
 
@@ -73,7 +137,44 @@ pub fn handle(day: Day, overwrite: bool) {
             process::exit(1);
         }
     }
+}
 
-    println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+/// Creates the module of an author in `src/bin/NN/` and registers it in the day's module.
+fn scaffold_author(puzzle: PuzzleId, author: &str, overwrite: bool) {
+    let dir = authors::dir(puzzle);
+    if let Err(e) = fs::create_dir_all(&dir) {
+        eprintln!("Failed to create directory \"{}\": {e}", dir.display());
+        process::exit(1);
+    }
+
+    let module_path = authors::module_path(puzzle, author).display().to_string();
+
+    let result = safe_create_file(&module_path, overwrite).and_then(|mut file| {
+        file.write_all(authors::module_template(&module_template()).as_bytes())
+    });
+    match result {
+        Ok(()) => println!("Created module file \"{module_path}\""),
+        Err(e) => {
+            eprintln!("Failed to create module file: {e}");
+            process::exit(1);
+        }
+    }
+
+    let day_path = puzzle.bin_path();
+    let registered = fs::read_to_string(&day_path).ok().and_then(|source| {
+        let registered = authors::register(&source, author)?;
+        Some(fs::write(&day_path, registered))
+    });
+    match registered {
+        Some(Ok(())) => println!("Registered {author} in \"{}\"", day_path.display()),
+        Some(Err(e)) => {
+            eprintln!("Failed to register {author}: {e}");
+            process::exit(1);
+        }
+        None => eprintln!(
+            "Could not register {author} automatically, add it to `solution!({}, authors = [...])` in \"{}\".",
+            puzzle.day.into_inner(),
+            day_path.display()
+        ),
+    }
 }
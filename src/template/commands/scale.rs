@@ -0,0 +1,34 @@
+use std::process::{self, Command, Stdio};
+
+use crate::template::PuzzleId;
+
+/// Runs the solution of a day with `--scale`, which times each part on generated inputs of growing size.
+/// Solutions register their generator with `solution!(NN, generator = <function>)`.
+pub fn handle(puzzle: PuzzleId) {
+    if !puzzle.bin_path().exists() {
+        eprintln!(
+            "Solution \"{}\" does not exist, scaffold it with `cargo scaffold {}`.",
+            puzzle.bin_path().display(),
+            puzzle.day
+        );
+        process::exit(1);
+    }
+
+    let status = Command::new("cargo")
+        .args([
+            "run",
+            "--release",
+            "--bin",
+            &puzzle.bin_name(),
+            "--",
+            "--scale",
+        ])
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .unwrap();
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
@@ -1,9 +1,23 @@
-use std::process::{Command, Stdio};
+use std::{
+    cmp::Ordering,
+    fs,
+    process::{self, Command, Stdio},
+};
 
-use crate::template::Day;
+use crate::template::{
+    answers, authors, named_inputs, run_multi::child_commands, runner::MAIN_AUTHOR,
+    timings::Timing, PuzzleId, ANSI_BOLD, ANSI_DIM, ANSI_GREEN, ANSI_RED, ANSI_RESET, ANSI_YELLOW,
+};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    puzzle: PuzzleId,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    author: Option<&str>,
+    variants: bool,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.bin_name()];
 
     if dhat {
         cmd_args.extend([
@@ -17,6 +31,17 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     }
 
     cmd_args.push("--".to_string());
+    // answers that match the accepted ones are recorded with the input, see `answers::record_reproduced`.
+    cmd_args.push("--record".to_string());
+
+    if let Some(author) = author {
+        cmd_args.push("--author".to_string());
+        cmd_args.push(author.to_string());
+    }
+
+    if variants {
+        cmd_args.push("--variants".to_string());
+    }
 
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
@@ -32,3 +57,487 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd.wait().unwrap();
 }
+
+/* -------------------------------------------------------------------------- */
+
+/// The outcome of a part on a named input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Check {
+    /// The part returned the expected answer.
+    Pass,
+    /// The part returned a different answer, or none at all.
+    Fail,
+    /// The part returned an answer, but none is expected.
+    Answered,
+    /// The part returned no answer, and none is expected.
+    Unsolved,
+}
+
+impl Check {
+    fn new(answer: Option<&String>, expected: Option<&String>) -> Self {
+        match (answer, expected) {
+            (Some(answer), Some(expected)) if answer == expected => Check::Pass,
+            (_, Some(_)) => Check::Fail,
+            (Some(_), None) => Check::Answered,
+            (None, None) => Check::Unsolved,
+        }
+    }
+
+    fn cell(&self) -> (&'static str, &'static str) {
+        match self {
+            Check::Pass => ("✔ pass", ANSI_GREEN),
+            Check::Fail => ("✖ fail", ANSI_RED),
+            Check::Answered => ("answered", ANSI_YELLOW),
+            Check::Unsolved => ("✖", ANSI_DIM),
+        }
+    }
+}
+
+/// The result of running a solution against one named input.
+struct InputRun {
+    name: String,
+    answers: [Option<String>; 2],
+    expected: [Option<String>; 2],
+    durations: [Option<String>; 2],
+}
+
+impl InputRun {
+    fn checks(&self) -> [Check; 2] {
+        [0, 1].map(|i| Check::new(self.answers[i].as_ref(), self.expected[i].as_ref()))
+    }
+}
+
+/// Runs both parts against every named input in `data/inputs/NN/` and compares them with the expected answers.
+pub fn handle_all_inputs(puzzle: PuzzleId, release: bool, author: Option<&str>) {
+    let inputs = match named_inputs::list(puzzle) {
+        Ok(inputs) => inputs,
+        Err(e) => {
+            eprintln!("Failed to read expected answers: {e}");
+            process::exit(1);
+        }
+    };
+
+    if inputs.is_empty() {
+        eprintln!(
+            "No inputs found in \"{}\". Add inputs like `alice.txt`, with expected answers in `alice.toml`.",
+            named_inputs::dir(puzzle).display()
+        );
+        process::exit(1);
+    }
+
+    let mut runs = vec![];
+
+    for input in inputs {
+        println!("Running {}...", input.name);
+        let path = input.path.to_string_lossy();
+        let mut bin_args = vec!["--input", &path];
+        if let Some(author) = author {
+            bin_args.extend(["--author", author]);
+        }
+
+        let output = match child_commands::capture_solution(puzzle, release, &bin_args) {
+            Ok(Some(output)) => output,
+            Ok(None) => {
+                eprintln!("Day {} has not been scaffolded yet.", puzzle.day);
+                process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("Failed to run day {}: {e:?}", puzzle.day);
+                process::exit(1);
+            }
+        };
+
+        runs.push(InputRun {
+            name: input.name,
+            answers: child_commands::parse_answers(&output),
+            expected: input.expected,
+            durations: child_commands::parse_durations(&output),
+        });
+    }
+
+    println!();
+    println!("{}", render(&runs));
+
+    if runs.iter().any(|run| run.checks().contains(&Check::Fail)) {
+        process::exit(1);
+    }
+}
+
+fn render(runs: &[InputRun]) -> String {
+    let width = runs
+        .iter()
+        .map(|run| run.name.chars().count())
+        .chain(["Input".len()])
+        .max()
+        .unwrap_or_default()
+        + 2;
+
+    let mut lines = vec![format!(
+        "{ANSI_BOLD}{:<width$}{:<12}{:<12}{:<12}{:<12}{ANSI_RESET}",
+        "Input", "Part 1", "Part 2", "Time 1", "Time 2"
+    )];
+    let mut failures = vec![];
+
+    for run in runs {
+        let mut line = format!("{:<width$}", run.name);
+
+        for (i, check) in run.checks().iter().enumerate() {
+            let (text, color) = check.cell();
+            line.push_str(&format!("{color}{text:<12}{ANSI_RESET}"));
+
+            if *check == Check::Fail {
+                failures.push(format!(
+                    "{}, part {}: expected {}, got {}",
+                    run.name,
+                    i + 1,
+                    run.expected[i].as_deref().unwrap_or_default(),
+                    run.answers[i].as_deref().unwrap_or("no answer"),
+                ));
+            }
+        }
+
+        for duration in &run.durations {
+            line.push_str(&format!("{:<12}", duration.as_deref().unwrap_or("-")));
+        }
+
+        lines.push(line.trim_end().to_string());
+    }
+
+    lines.push(String::new());
+    lines.extend(
+        failures
+            .iter()
+            .map(|f| format!("{ANSI_RED}✖{ANSI_RESET} {f}")),
+    );
+
+    let passed = runs
+        .iter()
+        .filter(|run| !run.checks().contains(&Check::Fail))
+        .count();
+    lines.push(format!(
+        "{ANSI_BOLD}Passed:{ANSI_RESET} {passed} / {} inputs",
+        runs.len()
+    ));
+
+    lines.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// How an author's answer relates to the reference answer of its part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Agreement {
+    /// The answer matches the answer Advent of Code accepted.
+    Verified,
+    /// The answer matches the most common answer among the authors.
+    Agrees,
+    Differs,
+    Unsolved,
+}
+
+impl Agreement {
+    fn new(answer: Option<&String>, reference: Option<&Reference>) -> Self {
+        match (answer, reference) {
+            (None, _) | (Some(_), None) => Agreement::Unsolved,
+            (Some(answer), Some(reference)) if *answer != reference.answer => Agreement::Differs,
+            (Some(_), Some(reference)) if reference.accepted => Agreement::Verified,
+            (Some(_), Some(_)) => Agreement::Agrees,
+        }
+    }
+
+    fn cell(self) -> (&'static str, &'static str) {
+        match self {
+            Agreement::Verified => ("✔ verified", ANSI_GREEN),
+            Agreement::Agrees => ("✔ agrees", ANSI_GREEN),
+            Agreement::Differs => ("✖ differs", ANSI_RED),
+            Agreement::Unsolved => ("✖", ANSI_DIM),
+        }
+    }
+}
+
+/// The answer every author should arrive at.
+struct Reference {
+    answer: String,
+    /// Whether this is the accepted answer, otherwise it is the most common one.
+    accepted: bool,
+}
+
+impl Reference {
+    /// The accepted answer if known, otherwise the most common answer. Ties go to the earlier author.
+    fn new(answers: &[Option<&String>], accepted: Option<&String>) -> Option<Self> {
+        if let Some(accepted) = accepted {
+            return Some(Reference {
+                answer: accepted.clone(),
+                accepted: true,
+            });
+        }
+
+        let mut counts: Vec<(&String, usize)> = vec![];
+        for answer in answers.iter().flatten() {
+            match counts.iter_mut().find(|(a, _)| a == answer) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((answer, 1)),
+            }
+        }
+
+        // `max_by_key` returns the last maximum, so iterate in reverse to prefer earlier authors.
+        counts
+            .into_iter()
+            .rev()
+            .max_by_key(|(_, count)| *count)
+            .map(|(answer, _)| Reference {
+                answer: answer.clone(),
+                accepted: false,
+            })
+    }
+}
+
+/// The result of running the solution of one author, see `cargo solve --compare`.
+struct AuthorRun {
+    author: String,
+    answers: [Option<String>; 2],
+    timing: Timing,
+}
+
+/// Runs and benches the solutions of all authors of a day, then checks that they agree on the answers.
+pub fn handle_compare(puzzle: PuzzleId, release: bool) {
+    let bin_path = puzzle.bin_path();
+    let Ok(source) = fs::read_to_string(&bin_path) else {
+        eprintln!("Day {} has not been scaffolded yet.", puzzle.day);
+        process::exit(1);
+    };
+
+    let mut names = vec![MAIN_AUTHOR.to_string()];
+    for author in authors::list(puzzle) {
+        if authors::is_registered(&source, &author) {
+            names.push(author);
+        } else {
+            eprintln!(
+                "Skipping {author}, it is not registered in \"{}\".",
+                bin_path.display()
+            );
+        }
+    }
+
+    if names.len() == 1 {
+        eprintln!(
+            "No other authors found for day {}. Add one with `cargo scaffold {} --author <name>`.",
+            puzzle.day, puzzle.day
+        );
+        process::exit(1);
+    }
+
+    let mut runs = vec![];
+
+    for author in names {
+        println!("Running {author}...");
+
+        let output = match child_commands::capture_solution(
+            puzzle,
+            release,
+            &["--time", "--author", &author],
+        ) {
+            Ok(output) => output.unwrap_or_default(),
+            Err(e) => {
+                eprintln!("Failed to run day {}: {e:?}", puzzle.day);
+                process::exit(1);
+            }
+        };
+
+        runs.push(AuthorRun {
+            answers: child_commands::parse_answers(&output),
+            timing: child_commands::parse_exec_time(&output, puzzle.day),
+            author,
+        });
+    }
+
+    let accepted = answers::read_accepted(puzzle).unwrap_or_default();
+    let (table, agree) = render_leaderboard(&runs, &accepted);
+
+    println!();
+    println!("{table}");
+
+    if !agree {
+        process::exit(1);
+    }
+}
+
+/// Renders the runs sorted by their total time. Returns whether all authors agree.
+fn render_leaderboard(runs: &[AuthorRun], accepted: &[String]) -> (String, bool) {
+    let references: Vec<Option<Reference>> = (0..2)
+        .map(|i| {
+            let answers: Vec<Option<&String>> =
+                runs.iter().map(|run| run.answers[i].as_ref()).collect();
+            Reference::new(&answers, accepted.get(i))
+        })
+        .collect();
+
+    // runs without any timings, e.g. because they crashed, go last.
+    let mut ranked: Vec<&AuthorRun> = runs.iter().collect();
+    ranked.sort_by(|a, b| {
+        let key = |run: &AuthorRun| (run.timing.total_nanos == 0.0, run.timing.total_nanos);
+        key(a).partial_cmp(&key(b)).unwrap_or(Ordering::Equal)
+    });
+
+    let width = runs
+        .iter()
+        .map(|run| run.author.chars().count())
+        .chain(["Author".len()])
+        .max()
+        .unwrap_or_default()
+        + 2;
+
+    let mut lines = vec![format!(
+        "{ANSI_BOLD}{:<4}{:<width$}{:<12}{:<12}{:<12}{:<12}{ANSI_RESET}Total",
+        "#", "Author", "Part 1", "Part 2", "Time 1", "Time 2"
+    )];
+    let mut disagreements = vec![];
+
+    for (rank, run) in ranked.iter().enumerate() {
+        let mut line = format!("{:<4}{:<width$}", rank + 1, run.author);
+
+        for (i, reference) in references.iter().enumerate() {
+            let agreement = Agreement::new(run.answers[i].as_ref(), reference.as_ref());
+            let (text, color) = agreement.cell();
+            line.push_str(&format!("{color}{text:<12}{ANSI_RESET}"));
+
+            if let (Agreement::Differs, Some(reference)) = (agreement, reference) {
+                disagreements.push(format!(
+                    "{}, part {}: {}, expected {} ({})",
+                    run.author,
+                    i + 1,
+                    run.answers[i].as_deref().unwrap_or_default(),
+                    reference.answer,
+                    if reference.accepted {
+                        "accepted answer"
+                    } else {
+                        "most common answer"
+                    }
+                ));
+            }
+        }
+
+        let timing = &run.timing;
+        for time in [&timing.part_1, &timing.part_2] {
+            line.push_str(&format!("{:<12}", time.as_deref().unwrap_or("-")));
+        }
+
+        if timing.total_nanos > 0.0 {
+            line.push_str(&format!("{:.2}ms", timing.total_nanos / 1_000_000_f64));
+        } else {
+            line.push('-');
+        }
+
+        lines.push(line);
+    }
+
+    lines.push(String::new());
+    lines.extend(
+        disagreements
+            .iter()
+            .map(|d| format!("{ANSI_RED}✖{ANSI_RESET} {d}")),
+    );
+
+    let agree = disagreements.is_empty();
+    if agree {
+        lines.push(format!("{ANSI_GREEN}✔{ANSI_RESET} All authors agree."));
+    }
+
+    (lines.join("\n"), agree)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, render_leaderboard, AuthorRun, Check, InputRun, Reference};
+    use crate::{day, template::timings::Timing};
+
+    #[test]
+    fn checks_answers() {
+        let answer = Some("42".to_string());
+        let other = Some("43".to_string());
+        assert_eq!(Check::new(answer.as_ref(), answer.as_ref()), Check::Pass);
+        assert_eq!(Check::new(answer.as_ref(), other.as_ref()), Check::Fail);
+        assert_eq!(Check::new(None, answer.as_ref()), Check::Fail);
+        assert_eq!(Check::new(answer.as_ref(), None), Check::Answered);
+        assert_eq!(Check::new(None, None), Check::Unsolved);
+    }
+
+    #[test]
+    fn renders_failures() {
+        let runs = [
+            InputRun {
+                name: "alice".into(),
+                answers: [Some("1".into()), Some("2".into())],
+                expected: [Some("1".into()), Some("2".into())],
+                durations: [Some("1.0µs".into()), Some("2.0µs".into())],
+            },
+            InputRun {
+                name: "bob".into(),
+                answers: [Some("3".into()), None],
+                expected: [Some("3".into()), Some("4".into())],
+                durations: [Some("1.0µs".into()), None],
+            },
+        ];
+        let table = render(&runs);
+        assert!(table.contains("bob, part 2: expected 4, got no answer"));
+        assert!(!table.contains("alice, part"));
+        assert!(table.ends_with("1 / 2 inputs"));
+    }
+
+    fn author_run(author: &str, answers: [Option<&str>; 2], total_nanos: f64) -> AuthorRun {
+        AuthorRun {
+            author: author.into(),
+            answers: answers.map(|a| a.map(ToString::to_string)),
+            timing: Timing {
+                day: day!(6),
+                part_1: Some(format!("{}ms", total_nanos / 2e6)),
+                part_2: Some(format!("{}ms", total_nanos / 2e6)),
+                total_nanos,
+                input_hash: None,
+            },
+        }
+    }
+
+    #[test]
+    fn picks_reference_answers() {
+        let (a, b) = ("1".to_string(), "2".to_string());
+        let reference = Reference::new(&[Some(&a), Some(&b), Some(&b)], None).unwrap();
+        assert_eq!(reference.answer, "2");
+        assert!(!reference.accepted);
+
+        let reference = Reference::new(&[Some(&a), Some(&b)], None).unwrap();
+        assert_eq!(reference.answer, "1");
+
+        let reference = Reference::new(&[Some(&a), Some(&a)], Some(&b)).unwrap();
+        assert_eq!(reference.answer, "2");
+        assert!(reference.accepted);
+
+        assert!(Reference::new(&[None, None], None).is_none());
+    }
+
+    #[test]
+    fn ranks_authors_by_time() {
+        let runs = [
+            author_run("main", [Some("1"), Some("2")], 4e6),
+            author_run("alice", [Some("1"), Some("2")], 2e6),
+            author_run("bob", [Some("1"), None], 0.0),
+        ];
+        let (table, agree) = render_leaderboard(&runs, &[]);
+        let ranks: Vec<&str> = table.lines().skip(1).take(3).collect();
+        assert!(ranks[0].starts_with("1   alice"));
+        assert!(ranks[1].starts_with("2   main"));
+        assert!(ranks[2].starts_with("3   bob"));
+        assert!(agree);
+    }
+
+    #[test]
+    fn reports_disagreements() {
+        let runs = [
+            author_run("main", [Some("1"), Some("2")], 4e6),
+            author_run("alice", [Some("1"), Some("3")], 2e6),
+        ];
+        let (table, agree) = render_leaderboard(&runs, &["1".into(), "2".into()]);
+        assert!(table.contains("alice, part 2: 3, expected 2 (accepted answer)"));
+        assert!(!agree);
+    }
+}
@@ -0,0 +1,265 @@
+use crate::template::{
+    all_days,
+    answers::{self, VerifiedAnswers},
+    crypto, fingerprint,
+    run_multi::child_commands,
+    timings::{Timing, Timings},
+    Day, PuzzleId, ANSI_BOLD, ANSI_DIM, ANSI_GREEN, ANSI_RED, ANSI_RESET, ANSI_YELLOW,
+};
+
+/// The state of a single solution part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PartStatus {
+    /// The solution was not executed, e.g. because the input is missing.
+    NotRun,
+    /// The part returned `None`.
+    Unsolved,
+    /// The part returned an answer that has not been accepted yet.
+    Answered,
+    /// The answer matches the accepted answer.
+    Verified,
+    /// The answer differs from the accepted answer.
+    Mismatch,
+}
+
+impl PartStatus {
+    fn from_answers(answer: Option<&String>, accepted: Option<&String>) -> Self {
+        match (answer, accepted) {
+            (None, _) => PartStatus::Unsolved,
+            (Some(_), None) => PartStatus::Answered,
+            (Some(answer), Some(accepted)) if answer == accepted => PartStatus::Verified,
+            (Some(_), Some(_)) => PartStatus::Mismatch,
+        }
+    }
+
+    fn cell(&self) -> (&'static str, &'static str) {
+        match self {
+            PartStatus::NotRun => ("-", ANSI_DIM),
+            PartStatus::Unsolved => ("✖", ANSI_DIM),
+            PartStatus::Answered => ("answered", ANSI_YELLOW),
+            PartStatus::Verified => ("✔ verified", ANSI_GREEN),
+            PartStatus::Mismatch => ("✖ mismatch", ANSI_RED),
+        }
+    }
+}
+
+/// Everything we know about the progress of a day.
+pub struct DayStatus {
+    pub day: Day,
+    pub has_bin: bool,
+    pub has_example: bool,
+    pub has_input: bool,
+    /// The input differs from the one the accepted answers were verified with.
+    pub input_changed: bool,
+    pub has_puzzle: bool,
+    pub parts: [PartStatus; 2],
+    pub stars: usize,
+    pub timing: Option<Timing>,
+}
+
+impl DayStatus {
+    fn collect(day: Day, timings: &Timings, verified: &VerifiedAnswers, run: bool) -> Self {
+        let puzzle = PuzzleId::from(day);
+        let has_bin = puzzle.bin_path().exists();
+        let has_input = crypto::exists(&puzzle.data_path("inputs", ".txt"));
+        let accepted = answers::read_accepted(puzzle);
+
+        let input_hash = fingerprint::input_hash(puzzle);
+        let verified = verified.get(day);
+        let input_changed = fingerprint::differs(
+            verified.map(|v| v.input_hash.as_str()),
+            input_hash.as_deref(),
+        );
+
+        let parts = if run && has_bin && has_input {
+            let output = child_commands::capture_solution(puzzle, true, &[])
+                .ok()
+                .flatten()
+                .unwrap_or_default();
+            let results = child_commands::parse_answers(&output);
+            let accepted = accepted.clone().unwrap_or_default();
+            [
+                PartStatus::from_answers(results[0].as_ref(), accepted.first()),
+                PartStatus::from_answers(results[1].as_ref(), accepted.get(1)),
+            ]
+        } else {
+            [PartStatus::NotRun, PartStatus::NotRun]
+        };
+
+        Self {
+            day,
+            has_bin,
+            has_example: puzzle.data_path("examples", ".txt").exists(),
+            has_input,
+            input_changed,
+            has_puzzle: accepted.is_some(),
+            parts,
+            stars: accepted.map_or(0, |a| a.len()),
+            timing: timings.data.iter().find(|t| t.day == day).cloned(),
+        }
+    }
+}
+
+pub fn handle(run: bool) {
+    let timings = Timings::read_from_file(None, None);
+    let verified = VerifiedAnswers::read_from_file(None);
+
+    let statuses: Vec<DayStatus> = all_days()
+        .map(|day| {
+            if run {
+                eprint!("\rChecking day {day}...");
+            }
+            DayStatus::collect(day, &timings, &verified, run)
+        })
+        .collect();
+
+    if run {
+        eprint!("\r                    \r");
+    }
+
+    println!("{}", render(&statuses));
+}
+
+/// Pads `text` to `width` before applying `color`, so escape codes do not break alignment.
+fn cell(text: &str, width: usize, color: &str) -> String {
+    format!("{color}{text:<width$}{ANSI_RESET}")
+}
+
+fn check(present: bool) -> String {
+    if present {
+        cell("✔", 7, ANSI_GREEN)
+    } else {
+        cell("✖", 7, ANSI_DIM)
+    }
+}
+
+fn render(statuses: &[DayStatus]) -> String {
+    let header = format!(
+        "{ANSI_BOLD}{:<5}  {:<7}{:<7}{:<7}{:<7}{:<12}{:<12}{:<7}{:<12}{:<12}{ANSI_RESET}",
+        "Day", "Bin", "Ex.", "Input", "Puzzle", "Part 1", "Part 2", "Stars", "Time 1", "Time 2"
+    );
+
+    let mut lines = vec![header];
+
+    for status in statuses {
+        let [part_1, part_2] = &status.parts;
+        let (part_1, part_1_color) = part_1.cell();
+        let (part_2, part_2_color) = part_2.cell();
+
+        let stars = "*".repeat(status.stars);
+        let (time_1, time_2) = status.timing.as_ref().map_or(("-", "-"), |t| {
+            (
+                t.part_1.as_deref().unwrap_or("-"),
+                t.part_2.as_deref().unwrap_or("-"),
+            )
+        });
+
+        lines.push(format!(
+            "{:<5}  {}{}{}{}{}{}{}{}{}",
+            status.day,
+            check(status.has_bin),
+            check(status.has_example),
+            if status.input_changed {
+                cell("⚠", 7, ANSI_RED)
+            } else {
+                check(status.has_input)
+            },
+            check(status.has_puzzle),
+            cell(part_1, 12, part_1_color),
+            cell(part_2, 12, part_2_color),
+            cell(&stars, 7, ANSI_YELLOW),
+            cell(time_1, 12, ""),
+            cell(time_2, 12, ""),
+        ));
+    }
+
+    let stars: usize = statuses.iter().map(|s| s.stars).sum();
+    lines.push(String::new());
+
+    let changed: Vec<String> = statuses
+        .iter()
+        .filter(|s| s.input_changed)
+        .map(|s| s.day.to_string())
+        .collect();
+    if !changed.is_empty() {
+        lines.push(format!(
+            "{ANSI_RED}⚠{ANSI_RESET} Inputs changed since their answers were verified: {}",
+            changed.join(", ")
+        ));
+    }
+
+    lines.push(format!(
+        "{ANSI_BOLD}Stars:{ANSI_RESET} {ANSI_YELLOW}{stars}{ANSI_RESET} / {}",
+        statuses.len() * 2
+    ));
+
+    lines.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, DayStatus, PartStatus};
+    use crate::day;
+
+    #[test]
+    fn compares_answers() {
+        let answer = Some("42".to_string());
+        let other = Some("43".to_string());
+        assert_eq!(
+            PartStatus::from_answers(None, answer.as_ref()),
+            PartStatus::Unsolved
+        );
+        assert_eq!(
+            PartStatus::from_answers(answer.as_ref(), None),
+            PartStatus::Answered
+        );
+        assert_eq!(
+            PartStatus::from_answers(answer.as_ref(), answer.as_ref()),
+            PartStatus::Verified
+        );
+        assert_eq!(
+            PartStatus::from_answers(answer.as_ref(), other.as_ref()),
+            PartStatus::Mismatch
+        );
+    }
+
+    #[test]
+    fn renders_stars_summary() {
+        let statuses = vec![DayStatus {
+            day: day!(1),
+            has_bin: true,
+            has_example: true,
+            has_input: true,
+            input_changed: false,
+            has_puzzle: true,
+            parts: [PartStatus::Verified, PartStatus::Answered],
+            stars: 1,
+            timing: None,
+        }];
+        let table = render(&statuses);
+        assert!(table.contains("✔ verified"));
+        assert!(table.contains("answered"));
+        assert!(table.ends_with("1\x1b[0m / 2"));
+    }
+
+    #[test]
+    fn flags_changed_inputs() {
+        let status = |day, input_changed| DayStatus {
+            day,
+            has_bin: true,
+            has_example: true,
+            has_input: true,
+            input_changed,
+            has_puzzle: true,
+            parts: [PartStatus::Mismatch, PartStatus::Mismatch],
+            stars: 2,
+            timing: None,
+        };
+        let table = render(&[status(day!(1), false), status(day!(6), true)]);
+        assert!(table.contains("⚠"));
+        assert!(table.contains("Inputs changed since their answers were verified: 06"));
+
+        let table = render(&[status(day!(1), false)]);
+        assert!(!table.contains("Inputs changed"));
+    }
+}
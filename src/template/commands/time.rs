@@ -1,11 +1,39 @@
 use std::collections::HashSet;
 
+use crate::template::config::config;
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{fingerprint, readme_benchmarks, Day, PuzzleId, Year};
+use crate::template::{ANSI_RESET, ANSI_YELLOW};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+pub fn handle(
+    year: Option<Year>,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    profile: Option<&str>,
+) {
+    // resolve the working set, so `--year` with the current year behaves like no `--year`.
+    let archive = year.and_then(|year| PuzzleId::new(year, crate::day!(1)).archive());
+    let stored_timings = Timings::read_from_file(archive, profile);
+    let all_days = || year.unwrap_or_else(Year::current).all_days();
+
+    // timings recorded with a different input are outdated and benched again.
+    let changed_inputs: HashSet<Day> = stored_timings
+        .data
+        .iter()
+        .filter(|timing| {
+            let current = fingerprint::input_hash(PuzzleId::from_opt(year, timing.day));
+            fingerprint::differs(timing.input_hash.as_deref(), current.as_deref())
+        })
+        .map(|timing| timing.day)
+        .collect();
+
+    for day in &changed_inputs {
+        eprintln!(
+            "{ANSI_YELLOW}Warning:{ANSI_RESET} the input of day {day} changed since its timings were recorded."
+        );
+    }
 
     let days_to_run = day.map_or_else(
         || {
@@ -14,21 +42,29 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
                 all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                    .filter(|day| {
+                        !stored_timings.is_day_complete(*day) || changed_inputs.contains(day)
+                    })
                     .collect()
             }
         },
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(&days_to_run, archive, true, true).unwrap();
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(archive, profile).unwrap();
+
+        // the readme lists the timings of the working set and of every archived year.
+        let mut tables = vec![(None, Timings::read_from_file(None, profile))];
+        for year in config().archived_years() {
+            tables.push((Some(year), Timings::read_from_file(Some(year), profile)));
+        }
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(Year::current(), tables) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -0,0 +1,117 @@
+use std::{
+    io::{stdout, Write},
+    process, thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::{
+    aoc_cli,
+    commands::{read, scaffold},
+    Day, PuzzleId, Year, ANSI_BOLD, ANSI_RESET,
+};
+
+/// Delays between download attempts. Inputs are sometimes not available right at unlock.
+const RETRY_DELAYS: [u64; 6] = [2, 4, 8, 16, 32, 60];
+
+pub fn handle(wait: bool) {
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        process::exit(1);
+    }
+
+    let day = if wait {
+        wait_for_unlock()
+    } else {
+        Day::today()
+    };
+
+    let Some(day) = day else {
+        eprintln!(
+            "`today` command can only be run between the 1st and \
+            the {}th of december. Please use `scaffold` with a specific day.",
+            Year::current().days()
+        );
+        process::exit(1);
+    };
+
+    let puzzle = PuzzleId::from(day);
+    scaffold::handle(puzzle, false, None);
+    download_with_retry(puzzle);
+    read::handle(puzzle);
+}
+
+/// Returns the day to work on, waiting for it to unlock if necessary.
+/// If today's puzzle has not been scaffolded yet it is used right away, otherwise we wait for the next one.
+fn wait_for_unlock() -> Option<Day> {
+    if let Some(today) = Day::today() {
+        if !PuzzleId::from(today).bin_path().exists() {
+            return Some(today);
+        }
+    }
+
+    let (day, unlock) = Day::next_unlock(SystemTime::now())?;
+    let mut stdout = stdout();
+
+    while let Ok(remaining) = unlock.duration_since(SystemTime::now()) {
+        print!(
+            "\r⏳ Day {day} unlocks in {ANSI_BOLD}{}{ANSI_RESET} ",
+            format_countdown(remaining)
+        );
+        let _ = stdout.flush();
+        thread::sleep(remaining.min(Duration::from_secs(1)));
+    }
+
+    println!("\r🎄 Day {day} is unlocked!{}", " ".repeat(24));
+    Some(day)
+}
+
+fn download_with_retry(puzzle: PuzzleId) {
+    for delay in RETRY_DELAYS {
+        match aoc_cli::download(puzzle) {
+            Ok(_) => return,
+            Err(e) => {
+                eprintln!("failed to call aoc-cli: {e} Retrying in {delay}s...");
+                thread::sleep(Duration::from_secs(delay));
+            }
+        }
+    }
+
+    if let Err(e) = aoc_cli::download(puzzle) {
+        eprintln!("failed to call aoc-cli: {e}");
+        process::exit(1);
+    }
+}
+
+fn format_countdown(duration: Duration) -> String {
+    // round up, so the countdown reaches zero exactly at unlock.
+    let secs = duration.as_secs() + u64::from(duration.subsec_nanos() > 0);
+    let (days, secs) = (secs / 86_400, secs % 86_400);
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    );
+
+    if days > 0 {
+        format!("{days}d {time}")
+    } else {
+        time
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::format_countdown;
+    use std::time::Duration;
+
+    #[test]
+    fn formats_countdown() {
+        assert_eq!(format_countdown(Duration::from_millis(61_500)), "00:01:02");
+        assert_eq!(
+            format_countdown(Duration::from_secs(3 * 86_400 + 3600)),
+            "3d 01:00:00"
+        );
+        assert_eq!(format_countdown(Duration::ZERO), "00:00:00");
+    }
+}
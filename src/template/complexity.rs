@@ -0,0 +1,135 @@
+/// Estimates the time complexity of a solution from timings at growing input sizes, see `cargo scale`.
+use std::fmt::Display;
+
+/// Common complexity classes, from fastest to slowest growth.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Class {
+    Constant,
+    Logarithmic,
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Cubic,
+    Exponential,
+}
+
+const CLASSES: [Class; 7] = [
+    Class::Constant,
+    Class::Logarithmic,
+    Class::Linear,
+    Class::Linearithmic,
+    Class::Quadratic,
+    Class::Cubic,
+    Class::Exponential,
+];
+
+impl Class {
+    /// Natural logarithm of the growth function at `n`.
+    fn ln_growth(self, n: f64) -> f64 {
+        match self {
+            Class::Constant => 0.0,
+            Class::Logarithmic => n.ln().max(1.0).ln(),
+            Class::Linear => n.ln(),
+            Class::Linearithmic => n.ln() + n.ln().max(1.0).ln(),
+            Class::Quadratic => 2.0 * n.ln(),
+            Class::Cubic => 3.0 * n.ln(),
+            Class::Exponential => n * 2_f64.ln(),
+        }
+    }
+}
+
+impl Display for Class {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Class::Constant => "O(1)",
+            Class::Logarithmic => "O(log n)",
+            Class::Linear => "O(n)",
+            Class::Linearithmic => "O(n log n)",
+            Class::Quadratic => "O(n²)",
+            Class::Cubic => "O(n³)",
+            Class::Exponential => "O(2ⁿ)",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Fit {
+    /// Slope of the timings on a log-log scale, i.e. `k` in `t ~ nᵏ`.
+    pub exponent: f64,
+    /// The class that explains the timings best.
+    pub class: Class,
+}
+
+/// Fits `(size, seconds)` samples. Returns `None` for less than three samples.
+pub fn fit(samples: &[(f64, f64)]) -> Option<Fit> {
+    if samples.len() < 3 {
+        return None;
+    }
+
+    let logs: Vec<(f64, f64)> = samples.iter().map(|(n, t)| (n.ln(), t.ln())).collect();
+    let count = logs.len() as f64;
+    let mean_x = logs.iter().map(|(x, _)| x).sum::<f64>() / count;
+    let mean_y = logs.iter().map(|(_, y)| y).sum::<f64>() / count;
+
+    let covariance: f64 = logs.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance: f64 = logs.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    if variance == 0.0 {
+        return None;
+    }
+
+    // for `t = c * f(n)`, `ln t - ln f(n)` is constant. The class with the least variance of it fits best.
+    let spread = |class: Class| {
+        let offsets: Vec<f64> = samples
+            .iter()
+            .map(|&(n, t)| t.ln() - class.ln_growth(n))
+            .collect();
+        let mean = offsets.iter().sum::<f64>() / count;
+        offsets.iter().map(|o| (o - mean).powi(2)).sum::<f64>()
+    };
+
+    let class = CLASSES
+        .into_iter()
+        .min_by(|a, b| spread(*a).total_cmp(&spread(*b)))?;
+
+    Some(Fit {
+        exponent: covariance / variance,
+        class,
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fit, Class};
+
+    fn samples(f: impl Fn(f64) -> f64) -> Vec<(f64, f64)> {
+        (3..12)
+            .map(|k| {
+                let n = f64::from(1 << k);
+                (n, 1e-9 * f(n))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fits_complexity_classes() {
+        let linear = fit(&samples(|n| 5.0 * n)).unwrap();
+        assert_eq!(linear.class, Class::Linear);
+        assert!((linear.exponent - 1.0).abs() < 1e-9);
+
+        let quadratic = fit(&samples(|n| 3.0 * n * n)).unwrap();
+        assert_eq!(quadratic.class, Class::Quadratic);
+        assert!((quadratic.exponent - 2.0).abs() < 1e-9);
+
+        assert_eq!(
+            fit(&samples(|n| n * n.ln())).unwrap().class,
+            Class::Linearithmic
+        );
+        assert_eq!(fit(&samples(|n| n.powi(3))).unwrap().class, Class::Cubic);
+    }
+
+    #[test]
+    fn needs_enough_samples() {
+        assert!(fit(&[(1.0, 1.0), (2.0, 2.0)]).is_none());
+        assert!(fit(&[(2.0, 1.0), (2.0, 2.0), (2.0, 3.0)]).is_none());
+    }
+}
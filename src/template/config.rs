@@ -0,0 +1,730 @@
+/// Project configuration, read from an optional `aoc.toml` in the repository root.
+///
+/// Every key can be overridden with an environment variable named `AOC_<SECTION>_<KEY>`,
+/// e.g. `AOC_BENCH_BUDGET_MS=500`. Top-level keys drop the section: `AOC_YEAR=2023`.
+use std::{
+    collections::BTreeMap,
+    env,
+    error::Error,
+    ffi::OsString,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+    time::Duration,
+};
+
+use toml::{Table, Value};
+
+use crate::template::{day::MAX_DAYS, Year};
+
+static CONFIG_FILE_PATH: &str = "aoc.toml";
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Config {
+    /// The event year, passed to aoc-cli. Defaults to the latest event.
+    pub year: Option<u16>,
+    pub days: Days,
+    pub paths: Paths,
+    pub bench: Bench,
+    pub defaults: Defaults,
+    pub scaffold: Scaffold,
+}
+
+/// Number of puzzles per event. Events up to 2024 had 25 puzzles, later ones have 12.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Days {
+    /// Used for every year without an explicit entry in `years`.
+    pub default: u8,
+    pub years: BTreeMap<u16, u8>,
+}
+
+/// Locations of solutions and data files, relative to the repository root.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Paths {
+    pub data: String,
+    pub inputs: String,
+    pub examples: String,
+    pub puzzles: String,
+    pub bin: String,
+    pub timings: String,
+    /// Answers verified against a specific input, see `answers.rs`.
+    pub answers: String,
+    pub readme: String,
+    /// File containing the key for encrypted inputs, see `cargo inputs`.
+    pub key: String,
+}
+
+/// Controls how long `--time` benches a solution part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bench {
+    pub budget_ms: u64,
+    pub min_samples: u128,
+    pub max_samples: u128,
+}
+
+/// Flags that are appended to a command when not passed on the command-line.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Defaults {
+    pub solve: Vec<String>,
+    pub all: Vec<String>,
+    pub time: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Scaffold {
+    /// Path to a custom module template. Uses the built-in `src/template.txt` if not set.
+    pub template: Option<String>,
+}
+
+impl Default for Days {
+    fn default() -> Self {
+        Self {
+            default: 25,
+            years: BTreeMap::from([(2025, 12)]),
+        }
+    }
+}
+
+impl Default for Paths {
+    fn default() -> Self {
+        Self {
+            data: "data".into(),
+            inputs: "inputs".into(),
+            examples: "examples".into(),
+            puzzles: "puzzles".into(),
+            bin: "src/bin".into(),
+            timings: "timings.json".into(),
+            answers: "answers.json".into(),
+            readme: "README.md".into(),
+            key: ".aoc-key".into(),
+        }
+    }
+}
+
+impl Default for Bench {
+    fn default() -> Self {
+        Self {
+            budget_ms: 1000,
+            min_samples: 10,
+            max_samples: 10000,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Returns the loaded configuration, reading it on first access.
+/// Panics if `aoc.toml` is invalid. Use [`init`] to handle errors gracefully.
+pub fn config() -> &'static Config {
+    CONFIG.get_or_init(|| Config::load().unwrap_or_else(|e| panic!("{e}")))
+}
+
+/// Loads and validates the configuration. Called once by `main.rs` before running a command.
+pub fn init() -> Result<&'static Config, ConfigError> {
+    if let Some(config) = CONFIG.get() {
+        return Ok(config);
+    }
+    let config = Config::load()?;
+    Ok(CONFIG.get_or_init(|| config))
+}
+
+/// Sets the top-level `year` key in `aoc.toml`, keeping comments and other settings in place.
+/// Creates the file if it does not exist. Does not affect the already loaded configuration.
+pub fn store_year(year: Year) -> io::Result<()> {
+    let content = fs::read_to_string(CONFIG_FILE_PATH).unwrap_or_default();
+    fs::write(CONFIG_FILE_PATH, replace_year(&content, year))
+}
+
+fn replace_year(content: &str, year: Year) -> String {
+    let year_line = format!("year = {year}");
+    let mut lines: Vec<String> = content.lines().map(ToString::to_string).collect();
+
+    // top-level keys are located before the first table header.
+    let top_level = lines
+        .iter()
+        .position(|line| line.trim_start().starts_with('['))
+        .unwrap_or(lines.len());
+
+    let existing = lines[..top_level]
+        .iter()
+        .position(|line| line.split('=').next().map(str::trim) == Some("year"));
+
+    match existing {
+        Some(i) => lines[i] = year_line,
+        None if top_level < lines.len() => {
+            lines.insert(top_level, year_line);
+            lines.insert(top_level + 1, String::new());
+        }
+        None => lines.push(year_line),
+    }
+
+    lines.join("\n") + "\n"
+}
+
+impl Config {
+    /// Reads `aoc.toml` (if present) and applies environment variable overrides.
+    pub fn load() -> Result<Self, ConfigError> {
+        let mut table = match fs::read_to_string(CONFIG_FILE_PATH) {
+            Ok(content) => content
+                .parse::<Table>()
+                .map_err(|e| ConfigError::Parse(e.to_string()))?,
+            Err(_) => Table::new(),
+        };
+
+        apply_env_overrides(&mut table, env::vars_os())?;
+        Self::from_table(&table)
+    }
+
+    fn from_table(table: &Table) -> Result<Self, ConfigError> {
+        let mut config = Config::default();
+
+        for (key, value) in table {
+            match (key.as_str(), value) {
+                ("year", value) => {
+                    let year = as_int(key, value)?;
+                    config.year = Some(u16::try_from(year).map_err(|_| {
+                        ConfigError::Invalid(key.clone(), "expected a valid year".into())
+                    })?);
+                }
+                ("days", Value::Table(days)) => config.days.apply(days)?,
+                ("paths", Value::Table(paths)) => config.paths.apply(paths)?,
+                ("bench", Value::Table(bench)) => config.bench.apply(bench)?,
+                ("defaults", Value::Table(defaults)) => config.defaults.apply(defaults)?,
+                ("scaffold", Value::Table(scaffold)) => config.scaffold.apply(scaffold)?,
+                ("days" | "paths" | "bench" | "defaults" | "scaffold", _) => {
+                    return Err(ConfigError::Invalid(key.clone(), "expected a table".into()));
+                }
+                _ => return Err(ConfigError::UnknownKey(key.clone())),
+            }
+        }
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        if let Some(year) = self.year {
+            if year < 2015 {
+                return Err(ConfigError::Invalid(
+                    "year".into(),
+                    "the first event took place in 2015".into(),
+                ));
+            }
+        }
+
+        let days = self.days.years.values().chain([&self.days.default]);
+        if days.clone().any(|&days| days == 0 || days > MAX_DAYS) {
+            return Err(ConfigError::Invalid(
+                "days".into(),
+                format!("the number of days must be between 1 and {MAX_DAYS}"),
+            ));
+        }
+
+        if self.bench.budget_ms == 0 {
+            return Err(ConfigError::Invalid(
+                "bench.budget_ms".into(),
+                "must be greater than zero".into(),
+            ));
+        }
+
+        if self.bench.min_samples == 0 || self.bench.min_samples > self.bench.max_samples {
+            return Err(ConfigError::Invalid(
+                "bench.min_samples".into(),
+                "must be between 1 and bench.max_samples".into(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Number of puzzles in the event of a given year.
+    pub fn days_in(&self, year: Year) -> u8 {
+        self.days
+            .years
+            .get(&year.into_inner())
+            .copied()
+            .unwrap_or(self.days.default)
+    }
+
+    /// The largest number of puzzles of any event, i.e. the highest valid day number.
+    pub fn max_days(&self) -> u8 {
+        self.days
+            .years
+            .values()
+            .copied()
+            .fold(self.days.default, u8::max)
+    }
+
+    /// Directory containing the data files of a year: `data` for the working set, `data/YYYY` for archived years.
+    pub fn data_dir(&self, archive: Option<Year>) -> PathBuf {
+        let data = Path::new(&self.paths.data);
+        match archive {
+            Some(year) => data.join(year.to_string()),
+            None => data.to_path_buf(),
+        }
+    }
+
+    /// Path of a data file, e.g. `data/inputs/01.txt`.
+    /// `folder` is one of `inputs`, `examples` or `puzzles` and is mapped to the configured directory.
+    pub fn data_path(&self, archive: Option<Year>, folder: &str, file_name: &str) -> PathBuf {
+        let folder = match folder {
+            "inputs" => &self.paths.inputs,
+            "examples" => &self.paths.examples,
+            "puzzles" => &self.paths.puzzles,
+            other => other,
+        };
+        self.data_dir(archive).join(folder).join(file_name)
+    }
+
+    /// Years that have been archived into `data/YYYY`, in ascending order.
+    pub fn archived_years(&self) -> Vec<Year> {
+        let mut years: Vec<Year> = fs::read_dir(&self.paths.data)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+            .collect();
+        years.sort_unstable();
+        years
+    }
+
+    /// Path of the solution binary source for a given bin name, e.g. `src/bin/01.rs`.
+    pub fn bin_path(&self, bin_name: &str) -> PathBuf {
+        Path::new(&self.paths.bin).join(format!("{bin_name}.rs"))
+    }
+
+    /// Path of the stored timings. Named profiles live in a directory next to the default file.
+    pub fn timings_path(&self, archive: Option<Year>, profile: Option<&str>) -> PathBuf {
+        let path = self.data_dir(archive).join(&self.paths.timings);
+        match profile {
+            Some(name) => path.with_extension("").join(format!("{name}.json")),
+            None => path,
+        }
+    }
+
+    /// Path of the verified answers of a year.
+    pub fn answers_path(&self, archive: Option<Year>) -> PathBuf {
+        self.data_dir(archive).join(&self.paths.answers)
+    }
+
+    pub fn bench_budget(&self) -> Duration {
+        Duration::from_millis(self.bench.budget_ms)
+    }
+
+    /// Default flags for a command, see [`Defaults`].
+    pub fn default_flags(&self, command: &str) -> &[String] {
+        match command {
+            "solve" => &self.defaults.solve,
+            "all" => &self.defaults.all,
+            "time" => &self.defaults.time,
+            _ => &[],
+        }
+    }
+}
+
+impl Days {
+    fn apply(&mut self, table: &Table) -> Result<(), ConfigError> {
+        for (key, value) in table {
+            let name = format!("days.{key}");
+            let days = u8::try_from(as_int(&name, value)?)
+                .map_err(|_| ConfigError::Invalid(name.clone(), "expected a day count".into()))?;
+            match key.as_str() {
+                "default" => self.default = days,
+                year => {
+                    let year = year
+                        .parse::<Year>()
+                        .map_err(|_| ConfigError::UnknownKey(name.clone()))?;
+                    self.years.insert(year.into_inner(), days);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Paths {
+    fn apply(&mut self, table: &Table) -> Result<(), ConfigError> {
+        for (key, value) in table {
+            let target = match key.as_str() {
+                "data" => &mut self.data,
+                "inputs" => &mut self.inputs,
+                "examples" => &mut self.examples,
+                "puzzles" => &mut self.puzzles,
+                "bin" => &mut self.bin,
+                "timings" => &mut self.timings,
+                "answers" => &mut self.answers,
+                "readme" => &mut self.readme,
+                "key" => &mut self.key,
+                _ => return Err(ConfigError::UnknownKey(format!("paths.{key}"))),
+            };
+            *target = as_string(&format!("paths.{key}"), value)?;
+        }
+        Ok(())
+    }
+}
+
+impl Bench {
+    fn apply(&mut self, table: &Table) -> Result<(), ConfigError> {
+        for (key, value) in table {
+            let name = format!("bench.{key}");
+            let value = u64::try_from(as_int(&name, value)?)
+                .map_err(|_| ConfigError::Invalid(name.clone(), "must not be negative".into()))?;
+            match key.as_str() {
+                "budget_ms" => self.budget_ms = value,
+                "min_samples" => self.min_samples = value.into(),
+                "max_samples" => self.max_samples = value.into(),
+                _ => return Err(ConfigError::UnknownKey(name)),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Defaults {
+    fn apply(&mut self, table: &Table) -> Result<(), ConfigError> {
+        for (key, value) in table {
+            let target = match key.as_str() {
+                "solve" => &mut self.solve,
+                "all" => &mut self.all,
+                "time" => &mut self.time,
+                _ => return Err(ConfigError::UnknownKey(format!("defaults.{key}"))),
+            };
+            *target = as_string_list(&format!("defaults.{key}"), value)?;
+        }
+        Ok(())
+    }
+}
+
+impl Scaffold {
+    fn apply(&mut self, table: &Table) -> Result<(), ConfigError> {
+        for (key, value) in table {
+            match key.as_str() {
+                "template" => self.template = Some(as_string("scaffold.template", value)?),
+                _ => return Err(ConfigError::UnknownKey(format!("scaffold.{key}"))),
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn as_int(key: &str, value: &Value) -> Result<i64, ConfigError> {
+    value
+        .as_integer()
+        .ok_or_else(|| ConfigError::Invalid(key.into(), "expected an integer".into()))
+}
+
+fn as_string(key: &str, value: &Value) -> Result<String, ConfigError> {
+    value
+        .as_str()
+        .map(ToString::to_string)
+        .ok_or_else(|| ConfigError::Invalid(key.into(), "expected a string".into()))
+}
+
+fn as_string_list(key: &str, value: &Value) -> Result<Vec<String>, ConfigError> {
+    let invalid = || ConfigError::Invalid(key.into(), "expected an array of strings".into());
+    value
+        .as_array()
+        .ok_or_else(invalid)?
+        .iter()
+        .map(|v| v.as_str().map(ToString::to_string).ok_or_else(invalid))
+        .collect()
+}
+
+/// Writes `AOC_*` environment variables into the parsed table.
+/// Values are parsed as TOML first (so `AOC_YEAR=2023` is an integer) and fall back to strings.
+/// Lists can be passed as TOML arrays or as whitespace separated words.
+/// Variables that are not valid UTF-8 are ignored, like any other variable that is not a configuration key.
+fn apply_env_overrides(
+    table: &mut Table,
+    vars: impl Iterator<Item = (OsString, OsString)>,
+) -> Result<(), ConfigError> {
+    const SECTIONS: [&str; 5] = ["days", "paths", "bench", "defaults", "scaffold"];
+
+    for (name, raw) in vars {
+        let (Ok(name), Ok(raw)) = (name.into_string(), raw.into_string()) else {
+            continue;
+        };
+        let Some(key) = name.strip_prefix("AOC_") else {
+            continue;
+        };
+        let key = key.to_lowercase();
+
+        let (section, key) = match SECTIONS
+            .iter()
+            .find_map(|s| key.strip_prefix(&format!("{s}_")).map(|k| (*s, k)))
+        {
+            Some((section, key)) => (Some(section), key.to_string()),
+            None if key == "year" => (None, key),
+            // other `AOC_*` variables (e.g. `AOC_SESSION`) are not configuration keys.
+            None => continue,
+        };
+
+        let value = if section == Some("defaults") {
+            raw.parse::<Value>()
+                .ok()
+                .filter(Value::is_array)
+                .unwrap_or_else(|| Value::Array(raw.split_whitespace().map(Value::from).collect()))
+        } else {
+            format!("v = {raw}")
+                .parse::<Table>()
+                .ok()
+                .and_then(|mut t| t.remove("v"))
+                .unwrap_or(Value::String(raw))
+        };
+
+        match section {
+            Some(section) => {
+                let entry = table
+                    .entry(section)
+                    .or_insert_with(|| Value::Table(Table::new()));
+                entry
+                    .as_table_mut()
+                    .ok_or_else(|| ConfigError::Invalid(section.into(), "expected a table".into()))?
+                    .insert(key, value);
+            }
+            None => {
+                table.insert(key, value);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, PartialEq)]
+pub enum ConfigError {
+    Parse(String),
+    UnknownKey(String),
+    Invalid(String, String),
+}
+
+impl Error for ConfigError {}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Parse(e) => write!(f, "could not parse {CONFIG_FILE_PATH}: {e}"),
+            ConfigError::UnknownKey(key) => {
+                write!(f, "unknown key `{key}` in {CONFIG_FILE_PATH}.")
+            }
+            ConfigError::Invalid(key, reason) => {
+                write!(
+                    f,
+                    "invalid value for `{key}` in {CONFIG_FILE_PATH}: {reason}."
+                )
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{apply_env_overrides, replace_year, Config, ConfigError};
+    use crate::template::Year;
+    use std::path::PathBuf;
+    use toml::Table;
+
+    fn parse(s: &str) -> Result<Config, ConfigError> {
+        Config::from_table(&s.parse::<Table>().unwrap())
+    }
+
+    fn parse_with_env(s: &str, vars: &[(&str, &str)]) -> Result<Config, ConfigError> {
+        let mut table = s.parse::<Table>().unwrap();
+        apply_env_overrides(&mut table, vars.iter().map(|(k, v)| (k.into(), v.into())))?;
+        Config::from_table(&table)
+    }
+
+    #[test]
+    fn uses_defaults_for_empty_file() {
+        assert_eq!(parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn parses_all_sections() {
+        let config = parse(
+            r#"
+            year = 2023
+
+            [paths]
+            data = "puzzles"
+            inputs = "in"
+
+            [bench]
+            budget_ms = 250
+            max_samples = 100
+
+            [defaults]
+            solve = ["--release"]
+
+            [scaffold]
+            template = "my_template.txt"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Some(2023));
+        assert_eq!(
+            config.data_path(None, "inputs", "01.txt"),
+            PathBuf::from("puzzles/in/01.txt")
+        );
+        assert_eq!(
+            config.data_path(None, "examples", "01.txt"),
+            PathBuf::from("puzzles/examples/01.txt")
+        );
+        assert_eq!(config.bench.budget_ms, 250);
+        assert_eq!(config.bench.max_samples, 100);
+        assert_eq!(config.default_flags("solve"), &["--release".to_string()]);
+        assert!(config.default_flags("all").is_empty());
+        assert_eq!(config.scaffold.template, Some("my_template.txt".into()));
+    }
+
+    #[test]
+    fn parses_days_per_year() {
+        let config = parse("[days]\ndefault = 24\n2023 = 20").unwrap();
+        assert_eq!(config.days_in(Year::new(2023).unwrap()), 20);
+        assert_eq!(config.days_in(Year::new(2025).unwrap()), 12);
+        assert_eq!(config.days_in(Year::new(2016).unwrap()), 24);
+        assert_eq!(config.max_days(), 24);
+
+        assert_eq!(
+            parse("[days]\ntwenty = 20"),
+            Err(ConfigError::UnknownKey("days.twenty".into()))
+        );
+        assert!(matches!(
+            parse("[days]\n2023 = 0"),
+            Err(ConfigError::Invalid(..))
+        ));
+
+        let config = parse_with_env("", &[("AOC_DAYS_2024", "12")]).unwrap();
+        assert_eq!(config.days_in(Year::new(2024).unwrap()), 12);
+    }
+
+    #[test]
+    fn replaces_year() {
+        let year = Year::new(2025).unwrap();
+        assert_eq!(
+            replace_year("# year = 2015\nyear = 2024\n\n[bench]\nyear = 1", year),
+            "# year = 2015\nyear = 2025\n\n[bench]\nyear = 1\n"
+        );
+        assert_eq!(
+            replace_year("# comment\n[bench]\nbudget_ms = 1", year),
+            "# comment\nyear = 2025\n\n[bench]\nbudget_ms = 1\n"
+        );
+        assert_eq!(replace_year("", year), "year = 2025\n");
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert_eq!(
+            parse("colour = true"),
+            Err(ConfigError::UnknownKey("colour".into()))
+        );
+        assert_eq!(
+            parse("[paths]\ninptus = \"x\""),
+            Err(ConfigError::UnknownKey("paths.inptus".into()))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        assert!(matches!(
+            parse("year = \"2023\""),
+            Err(ConfigError::Invalid(..))
+        ));
+        assert!(matches!(
+            parse("year = 1999"),
+            Err(ConfigError::Invalid(..))
+        ));
+        assert!(matches!(
+            parse("[bench]\nmin_samples = 100\nmax_samples = 10"),
+            Err(ConfigError::Invalid(..))
+        ));
+        assert!(matches!(
+            parse("[defaults]\nsolve = \"--release\""),
+            Err(ConfigError::Invalid(..))
+        ));
+    }
+
+    #[test]
+    fn applies_env_overrides() {
+        let config = parse_with_env(
+            "year = 2022\n[bench]\nbudget_ms = 250",
+            &[
+                ("AOC_YEAR", "2023"),
+                ("AOC_BENCH_BUDGET_MS", "500"),
+                ("AOC_PATHS_DATA", "other"),
+                ("AOC_DEFAULTS_TIME", "--all --store"),
+                ("AOC_SESSION", "ignored"),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Some(2023));
+        assert_eq!(config.bench.budget_ms, 500);
+        assert_eq!(config.paths.data, "other");
+        assert_eq!(
+            config.default_flags("time"),
+            &["--all".to_string(), "--store".to_string()]
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn ignores_non_utf8_env_vars() {
+        use std::{ffi::OsString, os::unix::ffi::OsStringExt};
+
+        let mut table = toml::Table::new();
+        let vars = [
+            (OsString::from("AOC_YEAR"), OsString::from_vec(vec![0xff])),
+            (
+                OsString::from_vec(b"AOC_\xff".to_vec()),
+                OsString::from("1"),
+            ),
+            (OsString::from("AOC_BENCH_BUDGET_MS"), OsString::from("500")),
+        ];
+        apply_env_overrides(&mut table, vars.into_iter()).unwrap();
+
+        let config = Config::from_table(&table).unwrap();
+        assert_eq!(config.year, None);
+        assert_eq!(config.bench.budget_ms, 500);
+    }
+
+    #[test]
+    fn rejects_unknown_env_keys() {
+        assert_eq!(
+            parse_with_env("", &[("AOC_BENCH_BUDGET", "500")]),
+            Err(ConfigError::UnknownKey("bench.budget".into()))
+        );
+    }
+
+    #[test]
+    fn builds_timings_paths() {
+        let config = Config::default();
+        let year = Year::new(2023);
+        assert_eq!(
+            config.timings_path(None, None),
+            PathBuf::from("data/timings.json")
+        );
+        assert_eq!(
+            config.timings_path(None, Some("ci")),
+            PathBuf::from("data/timings/ci.json")
+        );
+        assert_eq!(
+            config.timings_path(year, None),
+            PathBuf::from("data/2023/timings.json")
+        );
+        assert_eq!(
+            config.data_path(year, "inputs", "01.txt"),
+            PathBuf::from("data/2023/inputs/01.txt")
+        );
+    }
+}
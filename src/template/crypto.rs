@@ -0,0 +1,241 @@
+/// Authenticated encryption of puzzle inputs and descriptions, see `cargo inputs`.
+///
+/// Encrypted files are stored next to the plain file with an additional `.enc` extension, e.g. `01.txt.enc`.
+/// They consist of a format marker, a random nonce and the XChaCha20-Poly1305 ciphertext.
+/// The file name is authenticated as well, so blobs can not be swapped between days unnoticed.
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    XChaCha20Poly1305, XNonce,
+};
+
+use crate::template::config::config;
+
+/// Environment variable holding the key as 64 hex characters. Takes precedence over the key file.
+pub const KEY_ENV_VAR: &str = "AOC_INPUTS_KEY";
+pub const EXTENSION: &str = "enc";
+
+const MAGIC: &[u8] = b"aoc-enc1";
+const NONCE_LEN: usize = 24;
+
+#[derive(Debug)]
+pub enum CryptoError {
+    MissingKey,
+    InvalidKey(String),
+    Malformed,
+    /// The key is wrong or the file has been tampered with.
+    Decrypt,
+    IO(io::Error),
+}
+
+impl Error for CryptoError {}
+
+impl Display for CryptoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CryptoError::MissingKey => write!(
+                f,
+                "no key found, set {KEY_ENV_VAR} or create the key file \"{}\"",
+                config().paths.key
+            ),
+            CryptoError::InvalidKey(source) => {
+                write!(f, "the key in {source} is not a 64 character hex string")
+            }
+            CryptoError::Malformed => write!(f, "not an encrypted input file"),
+            CryptoError::Decrypt => write!(f, "wrong key or the file has been modified"),
+            CryptoError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for CryptoError {
+    fn from(e: io::Error) -> Self {
+        CryptoError::IO(e)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+pub struct Key(chacha20poly1305::Key);
+
+impl Key {
+    /// Reads the key from [`KEY_ENV_VAR`], or from the key file configured as `paths.key`.
+    pub fn load() -> Result<Self, CryptoError> {
+        if let Ok(hex) = env::var(KEY_ENV_VAR) {
+            return Self::from_hex(&hex).ok_or_else(|| CryptoError::InvalidKey(KEY_ENV_VAR.into()));
+        }
+
+        let path = &config().paths.key;
+        match fs::read_to_string(path) {
+            Ok(hex) => Self::from_hex(&hex).ok_or_else(|| CryptoError::InvalidKey(path.clone())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Err(CryptoError::MissingKey),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Creates a new random key.
+    pub fn generate() -> Self {
+        Self(XChaCha20Poly1305::generate_key(&mut OsRng))
+    }
+
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.trim();
+        if hex.len() != 64 || !hex.is_ascii() {
+            return None;
+        }
+
+        let mut key = chacha20poly1305::Key::default();
+        for (i, byte) in key.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+        }
+        Some(Self(key))
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+}
+
+/// Path of the encrypted file for a plain file, e.g. `data/inputs/01.txt.enc`.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".");
+    path.push(EXTENSION);
+    PathBuf::from(path)
+}
+
+/// The file name is used as associated data, which ties a blob to the file it was created from.
+fn associated_data(name: &str) -> &[u8] {
+    name.as_bytes()
+}
+
+pub fn encrypt(key: &Key, name: &str, plaintext: &[u8]) -> Vec<u8> {
+    let cipher = XChaCha20Poly1305::new(&key.0);
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+    let ciphertext = cipher
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: plaintext,
+                aad: associated_data(name),
+            },
+        )
+        .expect("encrypting in memory can not fail");
+
+    [MAGIC, nonce.as_slice(), &ciphertext].concat()
+}
+
+pub fn decrypt(key: &Key, name: &str, blob: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    let rest = blob.strip_prefix(MAGIC).ok_or(CryptoError::Malformed)?;
+    if rest.len() < NONCE_LEN {
+        return Err(CryptoError::Malformed);
+    }
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+    XChaCha20Poly1305::new(&key.0)
+        .decrypt(
+            XNonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad: associated_data(name),
+            },
+        )
+        .map_err(|_| CryptoError::Decrypt)
+}
+
+/// Whether a data file exists in plain or in encrypted form.
+pub fn exists(path: &Path) -> bool {
+    path.exists() || encrypted_path(path).exists()
+}
+
+/// Reads a data file, decrypting its `.enc` counterpart if the plain file does not exist.
+pub fn read_to_string(path: &Path) -> Result<String, CryptoError> {
+    match fs::read_to_string(path) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            let blob = match fs::read(encrypted_path(path)) {
+                Ok(blob) => blob,
+                // report the missing plain file rather than the missing blob.
+                Err(_) => return Err(e.into()),
+            };
+            let name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or_default();
+            let plaintext = decrypt(&Key::load()?, name, &blob)?;
+            String::from_utf8(plaintext).map_err(|_| CryptoError::Malformed)
+        }
+        result => Ok(result?),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decrypt, encrypt, encrypted_path, CryptoError, Key};
+    use std::path::Path;
+
+    #[test]
+    fn encrypts_and_decrypts() {
+        let key = Key::generate();
+        let blob = encrypt(&key, "01.txt", b"3   4\n4   3\n");
+        assert_ne!(&blob[..], b"3   4\n4   3\n");
+        assert_eq!(decrypt(&key, "01.txt", &blob).unwrap(), b"3   4\n4   3\n");
+    }
+
+    #[test]
+    fn uses_random_nonces() {
+        let key = Key::generate();
+        assert_ne!(encrypt(&key, "01.txt", b"1"), encrypt(&key, "01.txt", b"1"));
+    }
+
+    #[test]
+    fn rejects_wrong_key_name_and_tampering() {
+        let key = Key::generate();
+        let mut blob = encrypt(&key, "01.txt", b"input");
+
+        assert!(matches!(
+            decrypt(&Key::generate(), "01.txt", &blob),
+            Err(CryptoError::Decrypt)
+        ));
+        assert!(matches!(
+            decrypt(&key, "02.txt", &blob),
+            Err(CryptoError::Decrypt)
+        ));
+
+        let last = blob.len() - 1;
+        blob[last] ^= 1;
+        assert!(matches!(
+            decrypt(&key, "01.txt", &blob),
+            Err(CryptoError::Decrypt)
+        ));
+        assert!(matches!(
+            decrypt(&key, "01.txt", b"input"),
+            Err(CryptoError::Malformed)
+        ));
+    }
+
+    #[test]
+    fn parses_hex_keys() {
+        let key = Key::generate();
+        assert_eq!(Key::from_hex(&key.to_hex()).unwrap().to_hex(), key.to_hex());
+        assert!(Key::from_hex("abc").is_none());
+        assert!(Key::from_hex(&"z".repeat(64)).is_none());
+    }
+
+    #[test]
+    fn builds_encrypted_paths() {
+        assert_eq!(
+            encrypted_path(Path::new("data/inputs/01.txt")),
+            Path::new("data/inputs/01.txt.enc")
+        );
+    }
+}
@@ -1,15 +1,21 @@
 use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-#[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
+use crate::template::{config::config, Year};
 
-#[cfg(feature = "today")]
-const SERVER_UTC_OFFSET: i32 = -5;
+/// The most puzzles an event can have. Also the range validated by [`day!`](crate::day) at compile time.
+pub const MAX_DAYS: u8 = 25;
+
+/// Puzzles unlock at midnight in the timezone of the Advent of Code servers (UTC-5).
+const SERVER_UTC_OFFSET: i64 = -5;
+const SECONDS_PER_DAY: i64 = 86_400;
 
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
 ///
+/// Events may have fewer days, see [`Year::days`]. Which days are valid for an event is configured in `aoc.toml`.
+///
 /// # Display
 /// This value displays as a two digit number.
 ///
@@ -22,10 +28,10 @@ const SERVER_UTC_OFFSET: i32 = -5;
 pub struct Day(u8);
 
 impl Day {
-    /// Creates a [`Day`] from the provided value if it's in the valid range,
+    /// Creates a [`Day`] from the provided value if it's a day of any configured event,
     /// returns [`None`] otherwise.
     pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        if day == 0 || day > config().max_days() {
             return None;
         }
         Some(Self(day))
@@ -43,18 +49,79 @@ impl Day {
     }
 }
 
-#[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's a day of this year's event, `None` otherwise.
     pub fn today() -> Option<Self> {
-        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
-        let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
-            Self::new(u8::try_from(today.day()).ok()?)
+        Self::today_at(SystemTime::now())
+    }
+
+    /// Returns the day of advent at a given point in time, see [`Day::today`].
+    pub fn today_at(now: SystemTime) -> Option<Self> {
+        let (year, month, day) = server_date(now);
+        let day = u8::try_from(day).ok()?;
+        let event = Year::new(u16::try_from(year).ok()?)?;
+        if month == 12 && event.contains(day) {
+            Some(Self(day))
         } else {
             None
         }
     }
+
+    /// Returns the next puzzle that unlocks after `now` together with its unlock time.
+    /// After the last puzzle of the year has unlocked, this is the first puzzle of next year's event.
+    pub fn next_unlock(now: SystemTime) -> Option<(Self, SystemTime)> {
+        let (year, month, day) = server_date(now);
+        let last_day = u32::from(Year::new(u16::try_from(year).ok()?)?.days());
+
+        let (year, next_day) = match (month, day) {
+            (12, day) if day >= last_day => (year + 1, 1),
+            (12, day) => (year, day + 1),
+            _ => (year, 1),
+        };
+
+        let midnight =
+            days_from_civil(year, 12, next_day) * SECONDS_PER_DAY - SERVER_UTC_OFFSET * 3600;
+        let unlock = UNIX_EPOCH + Duration::from_secs(u64::try_from(midnight).ok()?);
+
+        Some((Self(u8::try_from(next_day).ok()?), unlock))
+    }
+}
+
+/// Returns `(year, month, day)` in the timezone of the Advent of Code servers.
+pub(crate) fn server_date(now: SystemTime) -> (i64, u32, u32) {
+    let secs = match now.duration_since(UNIX_EPOCH) {
+        Ok(d) => i64::try_from(d.as_secs()).unwrap_or(i64::MAX),
+        Err(e) => -i64::try_from(e.duration().as_secs()).unwrap_or(i64::MAX),
+    };
+    civil_from_days((secs + SERVER_UTC_OFFSET * 3600).div_euclid(SECONDS_PER_DAY))
+}
+
+/// Converts days since the unix epoch to a `(year, month, day)` date.
+/// See: <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Converts a `(year, month, day)` date to days since the unix epoch.
+/// See: <https://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let month = i64::from(month);
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
 }
 
 impl Display for Day {
@@ -94,26 +161,40 @@ impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        write!(
+            f,
+            "expecting a day number between 1 and {}",
+            config().max_days()
+        )
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of the current event, see [`Year::current`].
 pub fn all_days() -> AllDays {
     AllDays::new()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of an event, from the 1st to its last day.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
+    /// Iterates the days of the current event.
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self::of(Year::current())
+    }
+
+    /// Iterates the days of the event in the given year.
+    pub fn of(year: Year) -> Self {
+        Self {
+            current: 1,
+            last: year.days(),
+        }
     }
 }
 
@@ -121,10 +202,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and the configured day count is at most `MAX_DAYS`.
         let day = Day(self.current);
         self.current += 1;
 
@@ -135,6 +216,7 @@ impl Iterator for AllDays {
 /* -------------------------------------------------------------------------- */
 
 /// Creates a [`Day`] value in a const context.
+/// The day number is validated against the default range of 1 to 25 at compile time.
 #[macro_export]
 macro_rules! day {
     ($day:expr) => {{
@@ -154,7 +236,56 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, civil_from_days, days_from_civil, AllDays, Day};
+    use crate::template::Year;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    /// 2024-12-05T04:59:59Z, one second before day 5 unlocks.
+    fn before_day_5() -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_733_374_799)
+    }
+
+    #[test]
+    fn converts_civil_dates() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(20_062), (2024, 12, 5));
+        assert_eq!(days_from_civil(2024, 12, 5), 20_062);
+        assert_eq!(days_from_civil(2000, 2, 29), 11_016);
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+    }
+
+    #[test]
+    fn today_uses_server_timezone() {
+        assert_eq!(Day::today_at(before_day_5()), Day::new(4));
+        assert_eq!(
+            Day::today_at(before_day_5() + Duration::from_secs(1)),
+            Day::new(5)
+        );
+        // 2024-11-30T12:00:00Z
+        assert_eq!(
+            Day::today_at(UNIX_EPOCH + Duration::from_secs(1_732_968_000)),
+            None
+        );
+    }
+
+    #[test]
+    fn finds_next_unlock() {
+        let (day, at) = Day::next_unlock(before_day_5()).unwrap();
+        assert_eq!(day, Day::new(5).unwrap());
+        assert_eq!(at.duration_since(before_day_5()).unwrap().as_secs(), 1);
+
+        // 2024-11-30T12:00:00Z
+        let (day, at) = Day::next_unlock(UNIX_EPOCH + Duration::from_secs(1_732_968_000)).unwrap();
+        assert_eq!(day, Day::new(1).unwrap());
+        assert_eq!(at, UNIX_EPOCH + Duration::from_secs(1_733_029_200));
+
+        // 2024-12-25T12:00:00Z, 2024-12-26T12:00:00Z and 2024-12-31T23:00:00Z wait for 2025-12-01T05:00:00Z.
+        for now in [1_735_128_000, 1_735_214_400, 1_735_686_000] {
+            let (day, at) = Day::next_unlock(UNIX_EPOCH + Duration::from_secs(now)).unwrap();
+            assert_eq!(day, Day::new(1).unwrap());
+            assert_eq!(at, UNIX_EPOCH + Duration::from_secs(1_764_565_200));
+        }
+    }
 
     #[test]
     fn all_days_iterator() {
@@ -187,6 +318,22 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn iterates_shorter_events() {
+        let days: Vec<_> = AllDays::of(Year::new(2025).unwrap()).collect();
+        assert_eq!(days.len(), 12);
+        assert_eq!(days.last(), Some(&Day(12)));
+
+        // 2025-12-12T12:00:00Z, the last day of the 2025 event.
+        let last_day = UNIX_EPOCH + Duration::from_secs(1_765_540_800);
+        assert_eq!(Day::today_at(last_day), Some(Day(12)));
+        assert_eq!(
+            Day::next_unlock(last_day),
+            Some((Day(1), UNIX_EPOCH + Duration::from_secs(1_796_101_200)))
+        );
+        assert_eq!(Day::today_at(last_day + Duration::from_secs(86_400)), None);
+    }
 }
 
 /* -------------------------------------------------------------------------- */
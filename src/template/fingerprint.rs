@@ -0,0 +1,47 @@
+/// Content hashes of puzzle inputs.
+/// Stored with timings and verified answers to detect when an input has been swapped since.
+use sha2::{Digest, Sha256};
+
+use crate::template::{crypto, PuzzleId};
+
+/// SHA-256 of an input, as lowercase hex.
+pub fn hash(input: &str) -> String {
+    Sha256::digest(input.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Hash of the current input of a puzzle. `None` if the input is missing or can not be decrypted.
+pub fn input_hash(puzzle: PuzzleId) -> Option<String> {
+    crypto::read_to_string(&puzzle.data_path("inputs", ".txt"))
+        .ok()
+        .map(|input| hash(&input))
+}
+
+/// Whether a stored hash belongs to a different input. Records without a hash are not compared.
+pub fn differs(stored: Option<&str>, current: Option<&str>) -> bool {
+    matches!((stored, current), (Some(stored), Some(current)) if stored != current)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{differs, hash};
+
+    #[test]
+    fn hashes_inputs() {
+        assert_eq!(
+            hash("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_ne!(hash("abc\n"), hash("abc"));
+    }
+
+    #[test]
+    fn compares_hashes() {
+        assert!(differs(Some("a"), Some("b")));
+        assert!(!differs(Some("a"), Some("a")));
+        assert!(!differs(None, Some("a")));
+        assert!(!differs(Some("a"), None));
+    }
+}
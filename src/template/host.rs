@@ -0,0 +1,260 @@
+/// Collects information about the machine and toolchain that benchmarks are recorded on.
+use std::{collections::HashMap, fs, thread};
+use tinyjson::JsonValue;
+
+/// Describes where a set of timings came from.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HostInfo {
+    pub cpu: String,
+    pub cores: usize,
+    pub rustc: String,
+    pub target: String,
+    pub profile: String,
+    pub features: Vec<String>,
+}
+
+impl HostInfo {
+    /// Inspect the current machine. `profile` is the cargo profile solutions are built with, `build` describes the
+    /// solution binaries, see [`Build::from_line`].
+    pub fn detect(profile: &str, build: Build) -> Self {
+        Self {
+            cpu: cpu_model(),
+            cores: thread::available_parallelism().map_or(1, std::num::NonZero::get),
+            rustc: build.rustc,
+            target: build.target,
+            profile: profile.into(),
+            features: build.features,
+        }
+    }
+
+    /// Single line summary, used below the README benchmark table.
+    pub fn summary(&self) -> String {
+        let features = if self.features.is_empty() {
+            "none".into()
+        } else {
+            self.features.join(", ")
+        };
+
+        format!(
+            "{} ({} cores) · rustc {} · {} · {} · features: {}",
+            self.cpu, self.cores, self.rustc, self.target, self.profile, features
+        )
+    }
+}
+
+/// Reads the CPU model from `/proc/cpuinfo`. Falls back to the architecture name on other systems.
+fn cpu_model() -> String {
+    fs::read_to_string("/proc/cpuinfo")
+        .ok()
+        .and_then(|cpuinfo| parse_cpu_model(&cpuinfo))
+        .unwrap_or_else(|| std::env::consts::ARCH.into())
+}
+
+fn parse_cpu_model(cpuinfo: &str) -> Option<String> {
+    // x86 reports "model name", most ARM kernels report "Hardware" or "Model".
+    ["model name", "Hardware", "Model"].iter().find_map(|key| {
+        cpuinfo.lines().find_map(|line| {
+            let (k, v) = line.split_once(':')?;
+            (k.trim() == *key && !v.trim().is_empty()).then(|| v.trim().to_string())
+        })
+    })
+}
+
+/// Starts the line in which a timed solution reports its [`Build`].
+const BUILD_PREFIX: &str = "build:";
+
+/// The toolchain and features a binary was compiled with.
+/// Solutions are separate binaries, so they report their own build instead of the one of the `cargo time` binary.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Build {
+    pub rustc: String,
+    pub target: String,
+    pub features: Vec<String>,
+}
+
+impl Build {
+    /// The build of the running binary. The toolchain and features are captured by `build.rs`.
+    pub fn current() -> Self {
+        Self {
+            rustc: env!("AOC_RUSTC_VERSION").into(),
+            target: env!("AOC_TARGET").into(),
+            features: env!("AOC_FEATURES")
+                .split(',')
+                .filter(|f| !f.is_empty())
+                .map(String::from)
+                .collect(),
+        }
+    }
+
+    /// A single line like `build: 1.83.0 aarch64-apple-darwin dhat-heap`, printed by solutions that run with `--time`.
+    pub fn to_line(&self) -> String {
+        format!(
+            "{BUILD_PREFIX} {} {} {}",
+            self.rustc,
+            self.target,
+            self.features.join(",")
+        )
+    }
+
+    /// Parses a line of [`Build::to_line`], `None` for any other line of output.
+    pub fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line.strip_prefix(BUILD_PREFIX)?.split(' ').skip(1);
+        let rustc = fields.next()?.to_string();
+        let target = fields.next()?.to_string();
+        let features = fields
+            .next()?
+            .split(',')
+            .filter(|f| !f.is_empty())
+            .map(ToString::to_string)
+            .collect();
+
+        Some(Self {
+            rustc,
+            target,
+            features,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HostInfo> for JsonValue {
+    fn from(value: &HostInfo) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("cpu".into(), JsonValue::String(value.cpu.clone()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("cores".into(), JsonValue::Number(value.cores as f64));
+        map.insert("rustc".into(), JsonValue::String(value.rustc.clone()));
+        map.insert("target".into(), JsonValue::String(value.target.clone()));
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert(
+            "features".into(),
+            JsonValue::Array(
+                value
+                    .features
+                    .iter()
+                    .map(|f| JsonValue::String(f.clone()))
+                    .collect(),
+            ),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HostInfo {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected host to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("Expected host.{key} to be a string."))
+        };
+
+        let cores = json
+            .get("cores")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected host.cores to be a number.")?;
+
+        let features = json
+            .get("features")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected host.features to be an array.")?
+            .iter()
+            .map(|f| f.get::<String>().cloned())
+            .collect::<Option<Vec<_>>>()
+            .ok_or("Expected host.features to contain strings.")?;
+
+        Ok(HostInfo {
+            cpu: string("cpu")?,
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            cores: cores as usize,
+            rustc: string("rustc")?,
+            target: string("target")?,
+            profile: string("profile")?,
+            features,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_cpu_model, Build, HostInfo};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn parses_x86_cpu_model() {
+        let cpuinfo =
+            "processor\t: 0\nvendor_id\t: GenuineIntel\nmodel name\t: Intel(R) Core(TM) i7\n";
+        assert_eq!(
+            parse_cpu_model(cpuinfo),
+            Some("Intel(R) Core(TM) i7".into())
+        );
+    }
+
+    #[test]
+    fn parses_arm_cpu_model() {
+        let cpuinfo = "processor\t: 0\nBogoMIPS\t: 108.00\n\nModel\t\t: Raspberry Pi 4 Model B\n";
+        assert_eq!(
+            parse_cpu_model(cpuinfo),
+            Some("Raspberry Pi 4 Model B".into())
+        );
+    }
+
+    #[test]
+    fn roundtrips_build_lines() {
+        let build = Build {
+            rustc: "1.83.0".into(),
+            target: "aarch64-apple-darwin".into(),
+            features: vec!["dhat-heap".into(), "test_lib".into()],
+        };
+        assert_eq!(
+            build.to_line(),
+            "build: 1.83.0 aarch64-apple-darwin dhat-heap,test_lib"
+        );
+        assert_eq!(Build::from_line(&build.to_line()), Some(build));
+
+        let current = Build::current();
+        assert_eq!(Build::from_line(&current.to_line()), Some(current));
+        assert_eq!(Build::from_line("Part 1: 42 (1.2ms @ 100 samples)"), None);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let host = HostInfo {
+            cpu: "Apple M1".into(),
+            cores: 8,
+            rustc: "1.83.0".into(),
+            target: "aarch64-apple-darwin".into(),
+            profile: "release".into(),
+            features: vec!["dhat-heap".into()],
+        };
+        let json = JsonValue::from(&host);
+        assert_eq!(HostInfo::try_from(&json).unwrap(), host);
+    }
+
+    #[test]
+    fn formats_summary() {
+        let host = HostInfo {
+            cpu: "Apple M1".into(),
+            cores: 8,
+            rustc: "1.83.0".into(),
+            target: "aarch64-apple-darwin".into(),
+            profile: "release".into(),
+            features: vec![],
+        };
+        assert_eq!(
+            host.summary(),
+            "Apple M1 (8 cores) · rustc 1.83.0 · aarch64-apple-darwin · release · features: none"
+        );
+    }
+}
@@ -1,59 +1,117 @@
-use std::{env, fs};
-
+pub mod answers;
 pub mod aoc_cli;
+pub mod authors;
+pub mod cli;
 pub mod commands;
+pub mod config;
 pub mod runner;
 
 pub use day::*;
+pub use year::*;
 
+mod complexity;
+mod crypto;
 mod day;
+mod fingerprint;
+mod host;
+mod named_inputs;
 mod readme_benchmarks;
 mod run_multi;
 mod timings;
+mod year;
+
+use std::{fs, path::Path};
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
+pub const ANSI_YELLOW: &str = "\x1b[33m";
+pub const ANSI_DIM: &str = "\x1b[2m";
 
 /// Helper function that reads a text file to a string.
+/// Falls back to the encrypted `.enc` file if the plain file does not exist, see `cargo inputs`.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file(folder: &str, puzzle: impl Into<PuzzleId>) -> String {
+    let filepath = puzzle.into().data_path(folder, ".txt");
+    let f = crypto::read_to_string(&filepath);
+    f.unwrap_or_else(|e| panic!("could not open input file: {e}"))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file_part(folder: &str, puzzle: impl Into<PuzzleId>, part: u8) -> String {
+    let filepath = puzzle.into().data_path(folder, &format!("-{part}.txt"));
+    let f = crypto::read_to_string(&filepath);
+    f.unwrap_or_else(|e| panic!("could not open input file: {e}"))
+}
+
+/// Names of the files in a directory, without hidden files like `.keep`.
+pub(crate) fn file_names(dir: &Path) -> Vec<String> {
+    entry_names(dir, Path::is_file)
+}
+
+/// Names of the subdirectories of a directory, e.g. the named inputs in `data/inputs/NN/`.
+pub(crate) fn dir_names(dir: &Path) -> Vec<String> {
+    entry_names(dir, Path::is_dir)
+}
+
+fn entry_names(dir: &Path, filter: impl Fn(&Path) -> bool) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| filter(&entry.path()))
+        .filter_map(|entry| entry.file_name().to_str().map(ToString::to_string))
+        .filter(|name| !name.starts_with('.'))
+        .collect();
+    names.sort_unstable();
+    names
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Solutions of other authors live in `src/bin/NN/<author>.rs` and are registered with
+/// `solution!(NN, authors = [alice, bob])`. Run them with `cargo solve NN --author alice`.
+///
+/// Alternative implementations of a part are registered with `solution!(NN, part_one = [part_one_fast])`.
+/// `cargo solve NN --variants` benches them against the default `part_one` and checks their answers.
+///
+/// An input generator is registered with `solution!(NN, generator = generate)`, where `generate` is a
+/// `fn(&mut Rng, usize) -> String`. `cargo scale NN` uses it to estimate the complexity of each part.
+///
+/// Authors, variants and a generator can be combined, in this order, e.g.
+/// `solution!(NN, authors = [alice], part_one = [part_one_fast], generator = generate)`.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2] {} {} {});
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, [part_one, 1] {} {} {});
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, [part_two, 2] {} {} {});
+    };
+    ($day:expr $(, authors = [$($author:ident),+ $(,)?])? $(, part_one = [$($one:ident),+ $(,)?])? $(, part_two = [$($two:ident),+ $(,)?])? $(, generator = $generator:path)?) => {
+        // `mod` would look for the file next to the crate root, i.e. in `src/bin/` itself.
+        $($(
+            mod $author {
+                include!(concat!(
+                    env!("CARGO_MANIFEST_DIR"), "/src/bin/", env!("CARGO_BIN_NAME"), "/", stringify!($author), ".rs"
+                ));
+            }
+        )+)?
+        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2] $($( <$author> )+)? { $($($one)+)? } { $($($two)+)? } { $($generator)? });
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
-        /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+    (@impl $day:expr, $( [$func:expr, $part:expr] )* $( <$author:ident> )* { $($one:ident)* } { $($two:ident)* } { $($generator:path)? }) => {
+        /// The current day. Binaries named `YYYY-NN` belong to an archived year.
+        const DAY: $crate::template::PuzzleId =
+            $crate::template::PuzzleId::__from_bin_name(env!("CARGO_BIN_NAME"), $crate::day!($day));
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
@@ -61,8 +119,63 @@ macro_rules! solution {
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            // generated inputs replace the puzzle input, which might not even exist yet.
+            if scale_requested() {
+                $crate::solution!(@scale { $($generator)? } $( [$func, $part] )*);
+            }
+            let input = read_input(DAY);
+            report_build();
+            match author().as_deref() {
+                None | Some(MAIN_AUTHOR) if variants_requested() => {
+                    let (mut compared, mut agree) = (false, true);
+                    $crate::solution!(@variants compared, agree, &input, 1, part_one, $($one)*);
+                    $crate::solution!(@variants compared, agree, &input, 2, part_two, $($two)*);
+                    if !compared {
+                        eprintln!("No variants registered, add them with `solution!(<day>, part_one = [<function>, ...])`.");
+                    }
+                    if !agree {
+                        std::process::exit(1);
+                    }
+                }
+                None | Some(MAIN_AUTHOR) => {
+                    $( run_part($func, &input, DAY, $part); )*
+                }
+                $(
+                    Some(stringify!($author)) => {
+                        run_part($author::part_one, &input, DAY, 1);
+                        run_part($author::part_two, &input, DAY, 2);
+                    }
+                )*
+                Some(other) => unknown_author(other, &[$( stringify!($author) ),*]),
+            }
+        }
+    };
+
+    (@scale {} $( [$func:expr, $part:expr] )*) => {
+        eprintln!("No input generator registered, add one with `solution!(<day>, generator = <function>)`.");
+        std::process::exit(1);
+    };
+    (@scale { $generator:path } $( [$func:expr, $part:expr] )*) => {
+        let first = [$($part),*][0];
+        $(
+            if $part != first {
+                println!();
+            }
+            scale_part($func, $part, $generator);
+        )*
+        return;
+    };
+
+    (@variants $compared:ident, $agree:ident, $input:expr, $part:expr, $default:ident,) => {};
+    (@variants $compared:ident, $agree:ident, $input:expr, $part:expr, $default:ident, $($variant:ident)+) => {
+        if $compared {
+            println!();
         }
+        $compared = true;
+        $agree &= compare_variants(
+            $input,
+            $part,
+            &[(stringify!($default), $default), $( (stringify!($variant), $variant) ),+],
+        );
     };
 }
@@ -0,0 +1,114 @@
+/// Named inputs of a day in `data/inputs/NN/`, e.g. the inputs of everyone in a team.
+/// An input `<name>.txt` can have a sidecar `<name>.toml` with the answers it is expected to produce:
+///
+/// ```toml
+/// part_1 = 1234
+/// part_2 = "ABCD"
+/// ```
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use toml::{Table, Value};
+
+use crate::template::{crypto::EXTENSION, file_names, PuzzleId};
+
+pub struct NamedInput {
+    pub name: String,
+    pub path: PathBuf,
+    /// Expected answers of both parts, `None` if unknown.
+    pub expected: [Option<String>; 2],
+}
+
+/// Directory with the named inputs of a puzzle, e.g. `data/inputs/06`.
+pub fn dir(puzzle: PuzzleId) -> PathBuf {
+    puzzle.data_path("inputs", "")
+}
+
+/// Named inputs of a puzzle, sorted by name. Encrypted inputs are listed by their plain path.
+pub fn list(puzzle: PuzzleId) -> Result<Vec<NamedInput>, String> {
+    let dir = dir(puzzle);
+    let encrypted_suffix = format!(".txt.{EXTENSION}");
+
+    let mut names: Vec<String> = file_names(&dir)
+        .iter()
+        .filter_map(|file| {
+            file.strip_suffix(&encrypted_suffix)
+                .or_else(|| file.strip_suffix(".txt"))
+        })
+        .map(ToString::to_string)
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+
+    names
+        .into_iter()
+        .map(|name| {
+            Ok(NamedInput {
+                expected: read_expected(&dir.join(format!("{name}.toml")))?,
+                path: dir.join(format!("{name}.txt")),
+                name,
+            })
+        })
+        .collect()
+}
+
+fn read_expected(path: &Path) -> Result<[Option<String>; 2], String> {
+    match fs::read_to_string(path) {
+        Ok(content) => parse_expected(&content).map_err(|e| format!("\"{}\": {e}", path.display())),
+        Err(_) => Ok([None, None]),
+    }
+}
+
+fn parse_expected(content: &str) -> Result<[Option<String>; 2], String> {
+    let table = content.parse::<Table>().map_err(|e| e.to_string())?;
+    let mut expected = [None, None];
+
+    for (key, value) in table {
+        let part = match key.as_str() {
+            "part_1" => 0,
+            "part_2" => 1,
+            _ => return Err(format!("unknown key `{key}`, expecting part_1 or part_2.")),
+        };
+
+        expected[part] = Some(match value {
+            // multi-line answers are written as multi-line strings.
+            Value::String(answer) => answer.trim_end().to_string(),
+            Value::Integer(answer) => answer.to_string(),
+            other => {
+                return Err(format!(
+                    "`{key}` must be a string or an integer, found {}.",
+                    other.type_str()
+                ))
+            }
+        });
+    }
+
+    Ok(expected)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::parse_expected;
+
+    #[test]
+    fn parses_expected_answers() {
+        assert_eq!(
+            parse_expected("part_1 = 1234\npart_2 = \"ABCD\"").unwrap(),
+            [Some("1234".into()), Some("ABCD".into())]
+        );
+        assert_eq!(
+            parse_expected("part_2 = \"\"\"\n#..#\n.##.\n\"\"\"").unwrap(),
+            [None, Some("#..#\n.##.".into())]
+        );
+        assert_eq!(parse_expected("").unwrap(), [None, None]);
+    }
+
+    #[test]
+    fn rejects_invalid_sidecars() {
+        assert!(parse_expected("part_3 = 1").is_err());
+        assert!(parse_expected("part_1 = 1.5").is_err());
+        assert!(parse_expected("part_1 = ").is_err());
+    }
+}
@@ -3,7 +3,7 @@
 use std::{fs, io};
 
 use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::{config::config, PuzzleId, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -26,8 +26,8 @@ pub struct TablePosition {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: PuzzleId) -> String {
+    format!("./{}", puzzle.bin_path().display())
 }
 
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
@@ -52,19 +52,52 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// Timings of the working set (`None`) or of an archived year.
+pub type YearTimings = (Option<Year>, Timings);
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
+fn construct_table(prefix: &str, working_set: Year, tables: Vec<YearTimings>) -> String {
+    let mut tables: Vec<_> = tables
+        .into_iter()
+        .filter(|(archive, timings)| archive.is_none() || !timings.data.is_empty())
+        .collect();
+
+    // omit an empty working set, e.g. right after archiving a year.
+    if tables.len() > 1 {
+        tables.retain(|(archive, timings)| archive.is_some() || !timings.data.is_empty());
+    }
+
+    let mut lines: Vec<String> = vec![MARKER.into(), format!("{prefix} Benchmarks")];
+
+    if let [(None, _)] = tables.as_slice() {
+        let (archive, timings) = tables.remove(0);
+        construct_year_table(&mut lines, archive, timings);
+    } else {
+        // newest year first, the working set holds the current year.
+        tables.sort_by_key(|(archive, _)| std::cmp::Reverse(archive.unwrap_or(working_set)));
+        for (archive, timings) in tables {
+            lines.push(String::new());
+            lines.push(format!("{prefix}# {}", archive.unwrap_or(working_set)));
+            construct_year_table(&mut lines, archive, timings);
+        }
+    }
+
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+fn construct_year_table(lines: &mut Vec<String>, archive: Option<Year>, timings: Timings) {
+    let total_millis = timings.total_millis();
+    let host = timings.host;
+
+    lines.extend([
         String::new(),
         "| Day | Part 1 | Part 2 |".into(),
         "| :---: | :---: | :---:  |".into(),
-    ];
+    ]);
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+        let path = get_path_for_bin(PuzzleId::with_archive(archive, timing.day));
         lines.push(format!(
             "| [Day {}]({}) | `{}` | `{}` |",
             timing.day.into_inner(),
@@ -76,23 +109,30 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
 
-    lines.join("\n")
+    if let Some(host) = host {
+        lines.push(String::new());
+        lines.push(format!("_Measured on {}_", host.summary()));
+    }
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
+fn update_content(
+    s: &mut String,
+    working_set: Year,
+    tables: Vec<YearTimings>,
+) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+    let table = construct_table("##", working_set, tables);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
+/// Renders the benchmark tables. Archived years get a separate table each.
+/// `working_set` is the year of the timings without an archive year.
+pub fn update(working_set: Year, tables: Vec<YearTimings>) -> Result<(), Error> {
+    let path = &config().paths.readme;
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, working_set, tables)?;
     fs::write(path, &readme)?;
     Ok(())
 }
@@ -100,7 +140,10 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        day, template::host::HostInfo, template::timings::Timing, template::timings::Timings,
+        template::Year,
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -109,21 +152,25 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    total_nanos: 3e+7,
+                    input_hash: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    total_nanos: 7e+7,
+                    input_hash: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
+                    total_nanos: 9e+7,
+                    input_hash: None,
                 },
             ],
+            host: None,
         }
     }
 
@@ -131,36 +178,54 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::current(), vec![(None, get_mock_timings())]).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::current(), vec![(None, get_mock_timings())]).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::current(), vec![(None, get_mock_timings())]).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::current(), vec![(None, get_mock_timings())]).unwrap();
+        update_content(&mut s, Year::current(), vec![(None, get_mock_timings())]).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
 
+    #[test]
+    fn formats_host_metadata() {
+        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings.host = Some(HostInfo {
+            cpu: "Apple M1".into(),
+            cores: 8,
+            rustc: "1.83.0".into(),
+            target: "aarch64-apple-darwin".into(),
+            profile: "release".into(),
+            features: vec![],
+        });
+        update_content(&mut s, Year::current(), vec![(None, timings)]).unwrap();
+        assert!(s.contains(
+            "**Total: 190.00ms**\n\n_Measured on Apple M1 (8 cores) · rustc 1.83.0 · aarch64-apple-darwin · release · features: none_\n<!--- benchmarking table --->"
+        ));
+    }
+
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, Year::current(), vec![(None, get_mock_timings())]).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -180,4 +245,36 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_per_year() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let archived = Timings {
+            data: vec![Timing {
+                day: day!(7),
+                part_1: Some("1ms".into()),
+                part_2: None,
+                total_nanos: 1e+6,
+                input_hash: None,
+            }],
+            host: None,
+        };
+        let empty = Timings {
+            data: vec![],
+            host: None,
+        };
+        let tables = vec![
+            (Some(Year::new(2015).unwrap()), empty),
+            (Some(Year::new(2016).unwrap()), archived),
+            (None, get_mock_timings()),
+        ];
+        update_content(&mut s, Year::current(), tables).unwrap();
+
+        let current = format!("### {}", Year::current());
+        assert!(s.starts_with(&format!("{MARKER}\n## Benchmarks\n\n{current}\n\n| Day")));
+        assert!(s.contains("### 2016\n\n| Day | Part 1 | Part 2 |"));
+        assert!(s.contains("| [Day 7](./src/bin/2016-07.rs) | `1ms` | `-` |"));
+        assert!(!s.contains("### 2015"));
+        assert!(s.find(&current) < s.find("### 2016"));
+    }
 }
@@ -1,19 +1,31 @@
 use std::{collections::HashSet, io};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
-    all_days,
+    fingerprint,
+    host::{Build, HostInfo},
     timings::{Timing, Timings},
+    Day,
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Runs the solutions of the given days. `archive` selects an archived year, `None` the working set.
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    archive: Option<Year>,
+    is_release: bool,
+    is_timed: bool,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
+    // solutions are built separately from this binary and report their build when timed.
+    let mut build = None;
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    archive
+        .unwrap_or_else(Year::current)
+        .all_days()
         .filter(|day| days_to_run.contains(day))
         .for_each(|day| {
             if need_space {
@@ -24,18 +36,29 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let puzzle = PuzzleId::from_opt(archive, day);
+            let output = child_commands::run_solution(puzzle, is_timed, is_release).unwrap();
+            if build.is_none() {
+                build = output.iter().find_map(|l| Build::from_line(l));
+            }
 
             if output.is_empty() {
                 println!("Not solved.");
             } else {
-                let val = child_commands::parse_exec_time(&output, day);
+                let mut val = child_commands::parse_exec_time(&output, day);
+                val.input_hash = fingerprint::input_hash(puzzle);
                 timings.push(val);
             }
         });
 
     if is_timed {
-        let timings = Timings { data: timings };
+        let timings = Timings {
+            data: timings,
+            host: Some(HostInfo::detect(
+                if is_release { "release" } else { "dev" },
+                build.unwrap_or_else(Build::current),
+            )),
+        };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
@@ -59,32 +82,30 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::Error;
+    use crate::template::{host::Build, Day, PuzzleId, ANSI_BOLD, ANSI_RESET};
     use std::{
         io::{BufRead, BufReader},
-        path::Path,
         process::{Command, Stdio},
         thread,
     };
 
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    pub fn run_solution(
+        puzzle: PuzzleId,
+        is_timed: bool,
+        is_release: bool,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
+        if !puzzle.bin_path().exists() {
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let bin_name = puzzle.bin_name();
+        let mut args = vec!["run", "--quiet", "--bin", &bin_name];
 
         if is_release {
             args.push("--release");
@@ -118,7 +139,9 @@ pub mod child_commands {
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
+            if Build::from_line(&line).is_none() {
+                println!("{line}");
+            }
             output.push(line);
         }
 
@@ -128,12 +151,110 @@ pub mod child_commands {
         Ok(output)
     }
 
+    /// Run the solution bin for a given day without forwarding its output.
+    /// `bin_args` are passed on to the solution, e.g. `--input <path>`.
+    /// Returns `None` for days that have not been scaffolded yet.
+    pub fn capture_solution(
+        puzzle: PuzzleId,
+        is_release: bool,
+        bin_args: &[&str],
+    ) -> Result<Option<Vec<String>>, Error> {
+        if !puzzle.bin_path().exists() {
+            return Ok(None);
+        }
+
+        let bin_name = puzzle.bin_name();
+        let mut args = vec!["run", "--quiet", "--bin", &bin_name];
+
+        if is_release {
+            args.push("--release");
+        }
+
+        if !bin_args.is_empty() {
+            args.push("--");
+            args.extend(bin_args);
+        }
+
+        let output = Command::new("cargo")
+            .args(&args)
+            .stdin(Stdio::null())
+            .output()?;
+
+        Ok(Some(
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(ToString::to_string)
+                .collect(),
+        ))
+    }
+
+    /// Extract the results of both parts from the output of a solution bin.
+    pub fn parse_answers(output: &[String]) -> [Option<String>; 2] {
+        let mut answers = [None, None];
+        let mut lines = output.iter().peekable();
+
+        while let Some(line) = lines.next() {
+            // intermediate results are overwritten with a carriage return, keep the final one.
+            let line = line.rsplit('\r').next().unwrap_or_default();
+
+            let part = if line.starts_with("Part 1: ") {
+                0
+            } else if line.starts_with("Part 2: ") {
+                1
+            } else {
+                continue;
+            };
+
+            let result = &line["Part 1: ".len()..];
+
+            if result.starts_with('▼') {
+                // multi-line results are printed below the part label.
+                let mut result_lines = vec![];
+                while let Some(next) = lines.next_if(|l| !l.starts_with("Part ")) {
+                    result_lines.push(next.as_str());
+                }
+                answers[part] = Some(result_lines.join("\n").trim_end().to_string());
+            } else if let Some(result) = result.strip_prefix(ANSI_BOLD) {
+                answers[part] = result.split(ANSI_RESET).next().map(ToString::to_string);
+            }
+        }
+
+        answers
+    }
+
+    /// Extract the durations of both parts from the output of a single, non-benched run,
+    /// e.g. `1.2µs` from `Part 1: 42 (1.2µs)`.
+    pub fn parse_durations(output: &[String]) -> [Option<String>; 2] {
+        let mut durations = [None, None];
+
+        for line in output {
+            let line = line.rsplit('\r').next().unwrap_or_default();
+
+            let part = if line.starts_with("Part 1: ") {
+                0
+            } else if line.starts_with("Part 2: ") {
+                1
+            } else {
+                continue;
+            };
+
+            durations[part] = line
+                .trim_end()
+                .strip_suffix(')')
+                .and_then(|line| line.rsplit_once('('))
+                .map(|(_, duration)| duration.to_string());
+        }
+
+        durations
+    }
+
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            input_hash: None,
         };
 
         output
@@ -174,7 +295,7 @@ pub mod child_commands {
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
@@ -205,7 +326,7 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_answers, parse_durations, parse_exec_time};
 
         use crate::day;
 
@@ -239,6 +360,35 @@ pub mod child_commands {
             assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
+        #[test]
+        fn parses_answers() {
+            let res = parse_answers(&[
+                "Part 1: \x1b[1m42\x1b[0m\rPart 1: \x1b[1m42\x1b[0m (1.2µs)".into(),
+                "Part 2: ✖\rPart 2: ✖             ".into(),
+            ]);
+            assert_eq!(res, [Some("42".into()), None]);
+        }
+
+        #[test]
+        fn parses_multiline_answers() {
+            let res = parse_answers(&[
+                "Part 1: ▼ \rPart 1: ▼  (1.2µs)".into(),
+                "#..#".into(),
+                ".##.".into(),
+                "Part 2: \x1b[1mABC\x1b[0m (3.0ms)".into(),
+            ]);
+            assert_eq!(res, [Some("#..#\n.##.".into()), Some("ABC".into())]);
+        }
+
+        #[test]
+        fn parses_durations() {
+            let res = parse_durations(&[
+                "Part 1: \x1b[1m(42)\x1b[0m\rPart 1: \x1b[1m(42)\x1b[0m (1.2µs)".into(),
+                "Part 2: ✖\rPart 2: ✖             ".into(),
+            ]);
+            assert_eq!(res, [Some("1.2µs".into()), None]);
+        }
+
         #[test]
         fn parses_missing_parts() {
             let res = parse_exec_time(
@@ -2,14 +2,26 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
+use std::path::Path;
 use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
-use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::answers::{self, VerifiedAnswers};
+use crate::template::config::config;
+use crate::template::timings::Timings;
+use crate::template::{
+    aoc_cli, crypto, fingerprint, host::Build, read_file, PuzzleId, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::template::{complexity, ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_YELLOW};
+use crate::testing::Rng;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+pub fn run_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+) {
     let part_str = format!("Part {part}");
 
     let (result, duration, samples) =
@@ -18,13 +30,305 @@ pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, d
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        record_result(&result, puzzle, part);
+        submit_result(result, puzzle, part);
+    }
+}
+
+/// Records the accepted answers together with the input once a part reproduces one of them, see `answers.rs`.
+/// Only `cargo solve` passes `--record`, other commands like `cargo status --run` leave `data/answers.json` alone.
+fn record_result<T: Display>(result: &T, puzzle: PuzzleId, part: u8) {
+    let args: Vec<String> = env::args().collect();
+    if !args.contains(&"--record".into()) || args.contains(&"--input".into()) {
+        return;
+    }
+
+    if let Err(e) = answers::record_reproduced(puzzle, part, &result.to_string()) {
+        eprintln!("Failed to record the input fingerprint: {e}");
+    }
+}
+
+/// Prints how the solution was built when it is timed, so `cargo time` records the build of the solutions.
+pub fn report_build() {
+    if env::args().any(|x| x == "--time") {
+        println!("{}", Build::current().to_line());
+    }
+}
+
+/// Whether the solution was started with `--variants`, see `cargo solve --variants`.
+pub fn variants_requested() -> bool {
+    env::args().any(|x| x == "--variants")
+}
+
+/// A named implementation of a part, see `solution!(NN, part_one = [...])`.
+pub type Variant<'a, T> = (&'a str, fn(&str) -> Option<T>);
+
+/// A benched run of one variant of a part.
+struct VariantRun {
+    name: String,
+    answer: Option<String>,
+    duration: Duration,
+    samples: u128,
+}
+
+/// Benches the default solution of a part and its variants, then prints a comparison table with their relative time.
+/// Variants are named after their function, without the `part_one_` or `part_two_` prefix.
+/// Returns whether all variants return the same answer as the default.
+pub fn compare_variants<T: Display>(input: &str, part: u8, variants: &[Variant<T>]) -> bool {
+    let prefix = if part == 1 { "part_one_" } else { "part_two_" };
+    let mut runs = vec![];
+
+    for (i, (name, func)) in variants.iter().enumerate() {
+        let name = if i == 0 {
+            "default"
+        } else {
+            name.strip_prefix(prefix).unwrap_or(name)
+        };
+
+        print!("Part {part}: {name}");
+
+        let timer = Instant::now();
+        let answer = func(input).map(|answer| answer.to_string());
+        let base_time = timer.elapsed();
+        let (duration, samples) = bench(func, input, &base_time);
+
+        // clear the progress line.
+        print!("\r{}\r", " ".repeat(name.len() + 30));
+
+        runs.push(VariantRun {
+            name: name.to_string(),
+            answer,
+            duration,
+            samples,
+        });
+    }
+
+    let (table, agree) = format_variants(part, &runs);
+    println!("{table}");
+    agree
+}
+
+fn format_variants(part: u8, runs: &[VariantRun]) -> (String, bool) {
+    let width = runs
+        .iter()
+        .map(|run| run.name.len())
+        .chain(["Variant".len()])
+        .max()
+        .unwrap_or_default()
+        + 2;
+
+    let mut lines = vec![
+        format!("{ANSI_BOLD}Part {part}{ANSI_RESET}"),
+        format!(
+            "{ANSI_BOLD}{:<width$}{:<20}{:<28}Relative{ANSI_RESET}",
+            "Variant", "Answer", "Time"
+        ),
+    ];
+
+    let Some(default) = runs.first() else {
+        return (lines.join("\n"), true);
+    };
+    let mut differing = vec![];
+
+    for run in runs {
+        // multi-line answers are shortened to their first line.
+        let answer = match &run.answer {
+            Some(answer) if answer.contains('\n') => {
+                format!("{} …", answer.lines().next().unwrap_or_default())
+            }
+            Some(answer) => answer.clone(),
+            None => "✖".into(),
+        };
+        let time = format_duration(&run.duration, run.samples);
+        // time relative to the default, below 1 is faster.
+        let relative = run.duration.as_secs_f64() / default.duration.as_secs_f64().max(1e-9);
+
+        lines.push(format!(
+            "{:<width$}{answer:<20}{:<28}{}",
+            run.name,
+            time.trim().trim_start_matches('(').trim_end_matches(')'),
+            // keep a few significant digits for variants that are orders of magnitude faster.
+            if relative < 0.01 {
+                format!("{relative:.4}x")
+            } else {
+                format!("{relative:.2}x")
+            },
+        ));
+
+        if run.answer != default.answer {
+            differing.push(run.name.as_str());
+        }
+    }
+
+    let agree = differing.is_empty();
+    if agree {
+        lines.push(format!("{ANSI_GREEN}✔{ANSI_RESET} All variants agree."));
+    } else {
+        lines.push(format!(
+            "{ANSI_RED}✖{ANSI_RESET} Different answer than the default: {}",
+            differing.join(", ")
+        ));
+    }
+
+    (lines.join("\n"), agree)
+}
+
+/// Whether the solution was started with `--scale`, see `cargo scale`.
+pub fn scale_requested() -> bool {
+    env::args().any(|x| x == "--scale")
+}
+
+/// Builds an input of the given size, see `solution!(NN, generator = <function>)`.
+/// What the size means is up to the day, e.g. the number of lines or the side of a grid.
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+const SCALE_SEED: u64 = 2024;
+const SCALE_START_SIZE: usize = 8;
+const SCALE_MAX_SIZE: usize = 1 << 24;
+/// Fast runs are repeated for at least this long and averaged.
+const SCALE_SAMPLE_TIME: Duration = Duration::from_millis(50);
+/// Shorter timings are dominated by overhead and left out of the fit.
+const SCALE_MIN_FIT_TIME: Duration = Duration::from_micros(10);
+
+/// Times a part on generated inputs of doubling size, until a single run takes a quarter of the bench budget.
+/// Then fits the timings to common complexity classes and prints the estimated exponent.
+pub fn scale_part<T>(func: impl Fn(&str) -> Option<T>, part: u8, generate: Generator) {
+    let limit = config().bench_budget() / 4;
+    let mut samples = vec![];
+
+    println!("{ANSI_BOLD}Part {part}{ANSI_RESET}");
+    println!("{ANSI_BOLD}{:<12}Time{ANSI_RESET}", "Size");
+
+    let mut size = SCALE_START_SIZE;
+    while size <= SCALE_MAX_SIZE {
+        // the same seed for every size, so reruns are comparable.
+        let input = generate(&mut Rng::new(SCALE_SEED), size);
+        let duration = time_repeated(&func, &input);
+        println!("{size:<12}{duration:.1?}");
+
+        samples.push((size, duration));
+        if duration > limit {
+            break;
+        }
+        size *= 2;
+    }
+
+    println!("{}", format_fit(&samples));
+}
+
+fn time_repeated<T>(func: impl Fn(&str) -> Option<T>, input: &str) -> Duration {
+    let timer = Instant::now();
+    let mut runs = 0;
+
+    while runs == 0 || timer.elapsed() < SCALE_SAMPLE_TIME {
+        black_box(func(black_box(input)));
+        runs += 1;
+    }
+
+    timer.elapsed() / runs
+}
+
+fn format_fit(samples: &[(usize, Duration)]) -> String {
+    let points: Vec<(f64, f64)> = samples
+        .iter()
+        .filter(|(_, duration)| *duration >= SCALE_MIN_FIT_TIME)
+        .map(|(size, duration)| (*size as f64, duration.as_secs_f64()))
+        .collect();
+
+    match complexity::fit(&points) {
+        Some(fit) => format!(
+            "Estimated exponent: {ANSI_BOLD}{:.2}{ANSI_RESET} (best fit: {ANSI_BOLD}{}{ANSI_RESET})",
+            fit.exponent, fit.class
+        ),
+        None => format!(
+            "{ANSI_YELLOW}Not enough timings above {SCALE_MIN_FIT_TIME:?} to estimate the complexity.{ANSI_RESET}"
+        ),
+    }
+}
+
+/// Reads the input of a solution. `--input <path>` replaces the puzzle input with another file,
+/// see `cargo solve --all-inputs`.
+pub fn read_input(puzzle: PuzzleId) -> String {
+    let args: Vec<String> = env::args().collect();
+
+    let Some(index) = args.iter().position(|x| x == "--input") else {
+        let input = read_file("inputs", puzzle);
+        check_input(puzzle, &input);
+        return input;
+    };
+
+    let Some(path) = args.get(index + 1) else {
+        eprintln!("Unexpected command-line input. Format: cargo solve 1 -- --input <path>");
+        process::exit(1);
+    };
+
+    crypto::read_to_string(Path::new(path))
+        .unwrap_or_else(|e| panic!("could not open input file: {e}"))
+}
+
+/// Name of the solution in the day's own module, as opposed to the author modules in `src/bin/NN/`.
+pub const MAIN_AUTHOR: &str = "main";
+
+/// The author passed with `--author <name>`, see `cargo solve --author`.
+pub fn author() -> Option<String> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--author")?;
+
+    match args.get(index + 1) {
+        Some(author) => Some(author.clone()),
+        None => {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --author <name>");
+            process::exit(1);
+        }
+    }
+}
+
+/// Exits with a list of the registered authors of a day.
+pub fn unknown_author(author: &str, authors: &[&str]) -> ! {
+    let names: Vec<&str> = [MAIN_AUTHOR]
+        .into_iter()
+        .chain(authors.iter().copied())
+        .collect();
+    eprintln!(
+        "Unknown author `{author}`, expecting one of: {}.",
+        names.join(", ")
+    );
+    eprintln!("Authors are registered with `solution!(<day>, authors = [<name>, ...])`.");
+    process::exit(1);
+}
+
+/// Warns if the input differs from the one the stored answers or timings were recorded with.
+pub fn check_input(puzzle: PuzzleId, input: &str) {
+    let current = fingerprint::hash(input);
+    let path = puzzle.data_path("inputs", ".txt");
+
+    let verified = VerifiedAnswers::read_from_file(puzzle.archive());
+    let verified_hash = verified.get(puzzle.day).map(|v| v.input_hash.as_str());
+    if fingerprint::differs(verified_hash, Some(&current)) {
+        eprintln!(
+            "{ANSI_YELLOW}Warning:{ANSI_RESET} \"{}\" changed since its answers were accepted.",
+            path.display()
+        );
+    }
+
+    let timings = Timings::read_from_file(puzzle.archive(), None);
+    let timing_hash = timings
+        .data
+        .iter()
+        .find(|t| t.day == puzzle.day)
+        .and_then(|t| t.input_hash.as_deref());
+    if fingerprint::differs(timing_hash, Some(&current)) {
+        eprintln!(
+            "{ANSI_YELLOW}Warning:{ANSI_RESET} \"{}\" changed since its timings were recorded.",
+            path.display()
+        );
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///     The budget and sample bounds can be changed in the `[bench]` section of `aoc.toml`.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
@@ -58,8 +362,10 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let bench = &config().bench;
+    let bench_iterations = (config().bench_budget().as_nanos()
+        / cmp::max(base_time.as_nanos(), 10))
+    .clamp(bench.min_samples, bench.max_samples);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -134,7 +440,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 ///  2. aoc-cli is installed.
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    puzzle: PuzzleId,
     part: u8,
 ) -> Option<Result<Output, aoc_cli::AocCommandError>> {
     let args: Vec<String> = env::args().collect();
@@ -165,5 +471,59 @@ fn submit_result<T: Display>(
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(aoc_cli::submit(puzzle, part, &result.to_string()))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_fit, format_variants, VariantRun};
+    use std::time::Duration;
+
+    fn variant_run(name: &str, answer: Option<&str>, micros: u64) -> VariantRun {
+        VariantRun {
+            name: name.into(),
+            answer: answer.map(ToString::to_string),
+            duration: Duration::from_micros(micros),
+            samples: 100,
+        }
+    }
+
+    #[test]
+    fn compares_variants() {
+        let runs = [
+            variant_run("default", Some("2858"), 400),
+            variant_run("spans", Some("2858"), 100),
+            variant_run("ranges", Some("2858"), 2),
+        ];
+        let (table, agree) = format_variants(2, &runs);
+        assert!(agree);
+        assert!(table.contains("0.25x"));
+        assert!(table.contains("0.0050x"));
+        assert!(table.contains("1.00x"));
+        assert!(table.contains("100.0µs @ 100 samples"));
+    }
+
+    #[test]
+    fn reports_differing_variants() {
+        let runs = [
+            variant_run("default", Some("1928"), 400),
+            variant_run("fast", Some("1929"), 100),
+            variant_run("broken", None, 100),
+        ];
+        let (table, agree) = format_variants(1, &runs);
+        assert!(!agree);
+        assert!(table.ends_with("Different answer than the default: fast, broken"));
+    }
+
+    #[test]
+    fn estimates_complexity() {
+        let samples: Vec<_> = (3..14)
+            .map(|k| (1 << k, Duration::from_nanos(20 * (1 << (2 * k)))))
+            .collect();
+        assert!(format_fit(&samples).contains("2.00"));
+        assert!(format_fit(&samples).contains("O(n²)"));
+
+        let fast: Vec<_> = (3..14).map(|k| (1 << k, Duration::from_nanos(k))).collect();
+        assert!(format_fit(&fast).contains("Not enough timings"));
+    }
 }
@@ -1,9 +1,7 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
-
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+use crate::template::{config::config, host::HostInfo, Day, Year};
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -12,6 +10,8 @@ pub struct Timing {
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    /// Hash of the input the timings were recorded with, see [`fingerprint`](super::fingerprint).
+    pub input_hash: Option<String>,
 }
 
 /// Represents benchmark times for a set of days.
@@ -19,25 +19,34 @@ pub struct Timing {
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
     pub data: Vec<Timing>,
+    /// The machine the timings were last recorded on.
+    pub host: Option<HostInfo>,
 }
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// `archive` selects the timings of an archived year, `None` refers to the working set.
+    pub fn store_file(&self, archive: Option<Year>, profile: Option<&str>) -> Result<(), Error> {
+        // named profiles allow keeping timings of several hosts side by side.
+        let path = config().timings_path(archive, profile);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(path)?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+    pub fn read_from_file(archive: Option<Year>, profile: Option<&str>) -> Self {
+        fs::read_to_string(config().timings_path(archive, profile))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Host metadata is taken from `new`, as it describes the most recent run.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
@@ -51,8 +60,11 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        data.sort_unstable_by_key(|a| a.day);
+        Timings {
+            data,
+            host: new.host.clone().or_else(|| self.host.clone()),
+        }
     }
 
     /// Sum up total duration of timings as millis.
@@ -78,6 +90,10 @@ impl From<Timings> for JsonValue {
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
         );
 
+        if let Some(host) = &value.host {
+            map.insert("host".into(), JsonValue::from(host));
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -88,9 +104,11 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json_object = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let json_data = json_object
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
@@ -101,6 +119,11 @@ impl TryFrom<String> for Timings {
                 .iter()
                 .map(Timing::try_from)
                 .collect::<Result<_, _>>()?,
+            // older timing files do not contain host metadata.
+            host: json_object
+                .get("host")
+                .map(HostInfo::try_from)
+                .transpose()?,
         })
     }
 }
@@ -114,6 +137,10 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
+        if let Some(input_hash) = &value.input_hash {
+            map.insert("input_hash".into(), JsonValue::String(input_hash.clone()));
+        }
+
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
 
@@ -166,11 +193,21 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // older timing files do not contain input hashes.
+        let input_hash = json
+            .get("input_hash")
+            .map(|v| {
+                v.get::<String>()
+                    .ok_or("Expected timing.input_hash to be a string.")
+            })
+            .transpose()?;
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            input_hash: input_hash.cloned(),
         })
     }
 }
@@ -191,20 +228,24 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    input_hash: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    input_hash: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    input_hash: None,
                 },
             ],
+            host: None,
         }
     }
 
@@ -228,6 +269,24 @@ mod tests {
             let json = r#"{ "data": [] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 0);
+            assert_eq!(timings.host, None);
+        }
+
+        #[test]
+        fn handles_host_metadata() {
+            let json = r#"{ "data": [], "host": { "cpu": "Apple M1", "cores": 8, "rustc": "1.83.0", "target": "aarch64-apple-darwin", "profile": "release", "features": [] } }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let host = timings.host.unwrap();
+            assert_eq!(host.cpu, "Apple M1");
+            assert_eq!(host.cores, 8);
+        }
+
+        #[test]
+        fn handles_input_hashes() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000, "input_hash": "abc" }, { "day": "02", "part_1": null, "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].input_hash.as_deref(), Some("abc"));
+            assert_eq!(timings.data[1].input_hash, None);
         }
 
         #[test]
@@ -282,10 +341,12 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    input_hash: None,
                 }],
+                host: None,
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -296,10 +357,12 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    input_hash: None,
                 }],
+                host: None,
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -310,16 +373,19 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    input_hash: None,
                 }],
+                host: None,
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
+            template::host::HostInfo,
             template::timings::{Timing, Timings},
         };
 
@@ -334,7 +400,9 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    input_hash: None,
                 }],
+                host: None,
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -354,7 +422,9 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    input_hash: None,
                 }],
+                host: None,
             };
             let merged = timings.merge(&other);
 
@@ -373,6 +443,21 @@ mod tests {
             assert_eq!(merged.data.len(), 3);
         }
 
+        #[test]
+        fn keeps_latest_host() {
+            let timings = get_mock_timings();
+            let other = Timings {
+                data: vec![],
+                host: Some(HostInfo {
+                    cpu: "Apple M1".into(),
+                    ..HostInfo::default()
+                }),
+            };
+            let merged = timings.merge(&other);
+            assert_eq!(merged.host.unwrap().cpu, "Apple M1");
+            assert!(other.merge(&get_mock_timings()).host.is_some());
+        }
+
         #[test]
         fn handles_empty_other_timings() {
             let timings = get_mock_timings();
@@ -0,0 +1,252 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use crate::template::{config::config, day::server_date, AllDays, Day};
+
+/// The year of the first Advent of Code event.
+const FIRST_YEAR: u16 = 2015;
+
+/// A valid Advent of Code event year (i.e. 2015 or later).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's a valid event year,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < FIRST_YEAR {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The year of the working set, i.e. the solutions directly in `src/bin`.
+    /// Configured with the `year` key in `aoc.toml`, defaults to the latest event.
+    pub fn current() -> Self {
+        config()
+            .year
+            .and_then(Self::new)
+            .unwrap_or_else(|| Self::latest_at(SystemTime::now()))
+    }
+
+    /// Number of puzzles in this year's event, configured in the `[days]` section of `aoc.toml`.
+    pub fn days(self) -> u8 {
+        config().days_in(self)
+    }
+
+    /// Whether the event of this year has a puzzle on the given day.
+    pub fn contains(self, day: u8) -> bool {
+        (1..=self.days()).contains(&day)
+    }
+
+    /// An iterator over every day of this year's event.
+    pub fn all_days(self) -> AllDays {
+        AllDays::of(self)
+    }
+
+    /// The latest event that has started at a given point in time.
+    pub fn latest_at(now: SystemTime) -> Self {
+        let (year, month, _) = server_date(now);
+        let year = if month == 12 { year } else { year - 1 };
+        Self(u16::try_from(year).unwrap_or(FIRST_YEAR).max(FIRST_YEAR))
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting a year of {FIRST_YEAR} or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Identifies the puzzle of a day in a given year.
+///
+/// Puzzles of the working set (see [`Year::current`]) live in `src/bin/NN.rs` and `data/<folder>/NN.txt`.
+/// Puzzles of other years are archived in `src/bin/YYYY-NN.rs` and `data/YYYY/<folder>/NN.txt`.
+///
+/// # Display
+/// This value displays as the name of its solution binary, e.g. `08` or `2023-08`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    /// `None` for puzzles of the working set.
+    archive: Option<Year>,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    /// Creates the id of a puzzle. Puzzles of [`Year::current`] resolve to the working set.
+    pub fn new(year: Year, day: Day) -> Self {
+        let archive = (year != Year::current()).then_some(year);
+        Self { archive, day }
+    }
+
+    /// Creates the id of an archived puzzle (or of the working set if `archive` is `None`)
+    /// without comparing against [`Year::current`], e.g. while the current year changes.
+    pub(crate) fn with_archive(archive: Option<Year>, day: Day) -> Self {
+        Self { archive, day }
+    }
+
+    /// Creates the id of a puzzle in the given year, or in the working set if `year` is `None`.
+    pub fn from_opt(year: Option<Year>, day: Day) -> Self {
+        year.map_or_else(|| Self::from(day), |year| Self::new(year, day))
+    }
+
+    // Not part of the public API. Used by the `solution!` macro to infer the year from the binary name.
+    #[doc(hidden)]
+    pub const fn __from_bin_name(bin_name: &str, day: Day) -> Self {
+        let bytes = bin_name.as_bytes();
+
+        if bytes.len() != 7 || bytes[4] != b'-' {
+            return Self { archive: None, day };
+        }
+
+        let mut year: u16 = 0;
+        let mut i = 0;
+        while i < 4 {
+            let digit = bytes[i];
+            if !digit.is_ascii_digit() {
+                return Self { archive: None, day };
+            }
+            year = year * 10 + (digit - b'0') as u16;
+            i += 1;
+        }
+
+        Self {
+            archive: Some(Year(year)),
+            day,
+        }
+    }
+
+    /// The year this puzzle belongs to.
+    pub fn year(&self) -> Year {
+        self.archive.unwrap_or_else(Year::current)
+    }
+
+    /// The year of an archived puzzle, `None` for the working set.
+    pub fn archive(&self) -> Option<Year> {
+        self.archive
+    }
+
+    /// Name of the solution binary, e.g. `08` or `2023-08`.
+    pub fn bin_name(&self) -> String {
+        self.to_string()
+    }
+
+    /// Path of the solution module, e.g. `src/bin/08.rs`.
+    pub fn bin_path(&self) -> PathBuf {
+        config().bin_path(&self.bin_name())
+    }
+
+    /// Path of a data file named after the day, e.g. `data/2023/inputs/08.txt` for `folder = "inputs"`.
+    /// `suffix` is appended to the day number and includes the file extension, e.g. `.txt` or `-2.txt`.
+    pub fn data_path(&self, folder: &str, suffix: &str) -> PathBuf {
+        config().data_path(self.archive, folder, &format!("{}{suffix}", self.day))
+    }
+}
+
+impl From<Day> for PuzzleId {
+    fn from(day: Day) -> Self {
+        Self { archive: None, day }
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.archive {
+            Some(year) => write!(f, "{year}-{}", self.day),
+            None => write!(f, "{}", self.day),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PuzzleId, Year};
+    use crate::day;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn validates_years() {
+        assert!(Year::new(2014).is_none());
+        assert_eq!("2023".parse::<Year>().unwrap().into_inner(), 2023);
+        assert!("23".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn finds_latest_event() {
+        // 2024-11-30T12:00:00Z
+        let november = UNIX_EPOCH + Duration::from_secs(1_732_968_000);
+        assert_eq!(Year::latest_at(november), Year(2023));
+        // 2024-12-26T12:00:00Z
+        let december = UNIX_EPOCH + Duration::from_secs(1_735_214_400);
+        assert_eq!(Year::latest_at(december), Year(2024));
+    }
+
+    #[test]
+    fn parses_bin_names() {
+        let archived = PuzzleId::__from_bin_name("2023-08", day!(8));
+        assert_eq!(archived.archive(), Some(Year(2023)));
+        assert_eq!(archived.to_string(), "2023-08");
+
+        let current = PuzzleId::__from_bin_name("08", day!(8));
+        assert_eq!(current.archive(), None);
+        assert_eq!(current.to_string(), "08");
+
+        assert_eq!(
+            PuzzleId::__from_bin_name("abcd-08", day!(8)).archive(),
+            None
+        );
+    }
+
+    #[test]
+    fn builds_paths() {
+        let archived = PuzzleId::__from_bin_name("2023-08", day!(8));
+        assert_eq!(
+            archived.data_path("inputs", ".txt").to_str(),
+            Some("data/2023/inputs/08.txt")
+        );
+        assert_eq!(
+            archived.data_path("examples", "-2.txt").to_str(),
+            Some("data/2023/examples/08-2.txt")
+        );
+        assert_eq!(archived.bin_path().to_str(), Some("src/bin/2023-08.rs"));
+
+        let current = PuzzleId::from(day!(8));
+        assert_eq!(
+            current.data_path("puzzles", ".md").to_str(),
+            Some("data/puzzles/08.md")
+        );
+    }
+}
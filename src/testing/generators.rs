@@ -0,0 +1,223 @@
+/// Random puzzle inputs, together with functions to format them as puzzle text and to shrink them.
+/// Generators return structured values, so failing cases can be shrunk before they are formatted.
+use std::fmt::Write;
+
+use super::Rng;
+
+/// Smaller versions of a list: without its first or second half, then without each single element.
+pub fn shrink_vec<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    let mut candidates = vec![];
+    let half = items.len() / 2;
+
+    if half > 0 {
+        candidates.push(items[half..].to_vec());
+        candidates.push(items[..half].to_vec());
+    }
+
+    for i in 0..items.len() {
+        let mut candidate = items.to_vec();
+        candidate.remove(i);
+        candidates.push(candidate);
+    }
+
+    candidates
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A `width` x `height` grid. Cells are picked from `cells`, repeat a cell to make it more likely, e.g. `"....#"`.
+pub fn grid(rng: &mut Rng, width: usize, height: usize, cells: &str) -> Vec<Vec<char>> {
+    let cells: Vec<char> = cells.chars().collect();
+    (0..height)
+        .map(|_| (0..width).map(|_| *rng.pick(&cells)).collect())
+        .collect()
+}
+
+pub fn format_grid(grid: &[Vec<char>]) -> String {
+    grid.iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+/// Smaller versions of a grid without its first or last row or column. Grids never shrink below 1x1.
+pub fn shrink_grid(grid: &[Vec<char>]) -> Vec<Vec<Vec<char>>> {
+    let mut candidates = vec![];
+    let width = grid.first().map_or(0, Vec::len);
+
+    if grid.len() > 1 {
+        candidates.push(grid[1..].to_vec());
+        candidates.push(grid[..grid.len() - 1].to_vec());
+    }
+
+    if width > 1 {
+        candidates.push(grid.iter().map(|row| row[1..].to_vec()).collect());
+        candidates.push(grid.iter().map(|row| row[..width - 1].to_vec()).collect());
+    }
+
+    candidates
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// `len` numbers in `0..=max`.
+pub fn numbers(rng: &mut Rng, len: usize, max: u64) -> Vec<u64> {
+    (0..len).map(|_| rng.between(0, max)).collect()
+}
+
+pub fn format_numbers(numbers: &[u64], separator: &str) -> String {
+    let numbers: Vec<String> = numbers.iter().map(ToString::to_string).collect();
+    numbers.join(separator) + "\n"
+}
+
+/// Smaller versions of a number list: shorter lists, then lists with a single number halved.
+pub fn shrink_numbers(numbers: &[u64]) -> Vec<Vec<u64>> {
+    let mut candidates = shrink_vec(numbers);
+
+    for (i, &number) in numbers.iter().enumerate() {
+        if number > 0 {
+            let mut candidate = numbers.to_vec();
+            candidate[i] = number / 2;
+            candidates.push(candidate);
+        }
+    }
+
+    candidates
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A disk map of `files` files as in day 9: alternating file sizes (`1..=9`) and free space (`0..=9`).
+pub fn disk_map(rng: &mut Rng, files: usize) -> Vec<u8> {
+    let mut digits = vec![];
+    for i in 0..files {
+        digits.push(rng.between(1, 9) as u8);
+        if i + 1 < files {
+            digits.push(rng.between(0, 9) as u8);
+        }
+    }
+    digits
+}
+
+pub fn format_disk_map(digits: &[u8]) -> String {
+    digits.iter().map(|d| char::from(b'0' + d)).collect()
+}
+
+/// Smaller versions of a disk map: without the last file, without single files and with smaller digits.
+pub fn shrink_disk_map(digits: &[u8]) -> Vec<Vec<u8>> {
+    let mut candidates = vec![];
+
+    // files are at even positions, removing a file removes the free space after it as well.
+    for i in (0..digits.len()).step_by(2).rev() {
+        let mut candidate = digits.to_vec();
+        candidate.drain(i..(i + 2).min(digits.len()));
+        if candidate.len().is_multiple_of(2) {
+            candidate.pop();
+        }
+        if !candidate.is_empty() {
+            candidates.push(candidate);
+        }
+    }
+
+    for (i, &digit) in digits.iter().enumerate() {
+        let min = if i % 2 == 0 { 1 } else { 0 };
+        if digit > min {
+            let mut candidate = digits.to_vec();
+            candidate[i] = digit - 1;
+            candidates.push(candidate);
+        }
+    }
+
+    candidates
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A calibration equation as in day 7, e.g. `190: 10 19`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Equation {
+    pub test_value: u64,
+    pub numbers: Vec<u64>,
+}
+
+/// An equation of `len` numbers in `1..=max`. About half of them can be solved with `+` and `*`.
+pub fn equation(rng: &mut Rng, len: usize, max: u64) -> Equation {
+    let numbers: Vec<u64> = (0..len.max(1)).map(|_| rng.between(1, max)).collect();
+
+    let test_value = if rng.chance(1, 2) {
+        numbers[1..].iter().fold(numbers[0], |acc, &n| {
+            if rng.chance(1, 2) {
+                acc.saturating_add(n)
+            } else {
+                acc.saturating_mul(n)
+            }
+        })
+    } else {
+        rng.between(1, max.saturating_pow(len as u32))
+    };
+
+    Equation {
+        test_value,
+        numbers,
+    }
+}
+
+pub fn format_equations(equations: &[Equation]) -> String {
+    let mut text = String::new();
+    for equation in equations {
+        let numbers: Vec<String> = equation.numbers.iter().map(ToString::to_string).collect();
+        let _ = writeln!(text, "{}: {}", equation.test_value, numbers.join(" "));
+    }
+    text
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_grids() {
+        let grid = grid(&mut Rng::new(1), 3, 2, ".#");
+        assert_eq!(grid.len(), 2);
+        assert!(grid.iter().all(|row| row.len() == 3));
+        assert_eq!(format_grid(&grid).lines().count(), 2);
+        assert!(shrink_grid(&grid)
+            .iter()
+            .all(|g| g.len() * g[0].len() == 3 || g.len() * g[0].len() == 4));
+    }
+
+    #[test]
+    fn generates_disk_maps() {
+        let mut rng = Rng::new(2);
+        for files in 1..20 {
+            let digits = disk_map(&mut rng, files);
+            assert_eq!(digits.len(), files * 2 - 1);
+            assert!(digits.iter().step_by(2).all(|&d| d >= 1));
+
+            for candidate in shrink_disk_map(&digits) {
+                assert_eq!(candidate.len() % 2, 1);
+                assert!(candidate.iter().step_by(2).all(|&d| d >= 1));
+            }
+        }
+        assert_eq!(format_disk_map(&[1, 2, 3]), "123");
+    }
+
+    #[test]
+    fn formats_numbers_and_equations() {
+        assert_eq!(format_numbers(&[125, 17], " "), "125 17\n");
+        let equations = [Equation {
+            test_value: 190,
+            numbers: vec![10, 19],
+        }];
+        assert_eq!(format_equations(&equations), "190: 10 19\n");
+        assert!(shrink_numbers(&[4, 0]).contains(&vec![2, 0]));
+    }
+
+    #[test]
+    fn shrinks_lists() {
+        assert_eq!(
+            shrink_vec(&[1, 2, 3]),
+            vec![vec![2, 3], vec![1], vec![2, 3], vec![1, 3], vec![1, 2]]
+        );
+        assert!(shrink_vec::<u8>(&[]).is_empty());
+    }
+}
@@ -0,0 +1,156 @@
+/// Support for cross-checking optimized solutions against a reference implementation on random inputs.
+///
+/// ```ignore
+/// use advent_of_code::testing::{cross_check, disk_map, format_disk_map, shrink_disk_map};
+///
+/// cross_check(
+///     disk_map,
+///     |digits| shrink_disk_map(digits),
+///     |digits| part_one_naive(&format_disk_map(digits)),
+///     |digits| part_one(&format_disk_map(digits)),
+/// );
+/// ```
+///
+/// Inputs are generated from a fixed seed, so failures are reproducible. Set `AOC_CHECK_SEED` to try other seeds
+/// and `AOC_CHECK_CASES` to change the number of generated inputs.
+use std::{env, fmt::Debug, str::FromStr};
+
+mod generators;
+mod rng;
+
+pub use generators::*;
+pub use rng::Rng;
+
+const DEFAULT_SEED: u64 = 2024;
+
+/// Settings of a cross-check. Use [`cross_check`] for the defaults.
+#[derive(Clone, Debug)]
+pub struct CrossCheck {
+    /// Number of generated inputs.
+    pub cases: usize,
+    /// The size passed to the generator grows from 1 to `max_size` over the cases.
+    pub max_size: usize,
+    pub seed: u64,
+}
+
+impl Default for CrossCheck {
+    fn default() -> Self {
+        Self {
+            cases: from_env::<usize>("AOC_CHECK_CASES").unwrap_or(1000),
+            max_size: 30,
+            seed: from_env::<u64>("AOC_CHECK_SEED").unwrap_or(DEFAULT_SEED),
+        }
+    }
+}
+
+impl CrossCheck {
+    /// Runs `reference` and `optimized` on generated inputs and panics with the smallest failing input found.
+    pub fn run<T, R>(
+        &self,
+        generate: impl Fn(&mut Rng, usize) -> T,
+        shrink: impl Fn(&T) -> Vec<T>,
+        reference: impl Fn(&T) -> R,
+        optimized: impl Fn(&T) -> R,
+    ) where
+        T: Debug,
+        R: PartialEq + Debug,
+    {
+        let mut rng = Rng::new(self.seed);
+        let fails = |input: &T| reference(input) != optimized(input);
+
+        for case in 0..self.cases {
+            let size = 1 + case * self.max_size / self.cases.max(1);
+            let input = generate(&mut rng, size);
+
+            if !fails(&input) {
+                continue;
+            }
+
+            let (input, steps) = shrink_failure(input, &shrink, fails);
+            panic!(
+                "cross-check failed in case {} of {} (seed {}, rerun with AOC_CHECK_SEED={}).\n\
+                 Input, shrunk in {steps} steps: {input:?}\n\
+                 reference: {:?}\n\
+                 optimized: {:?}",
+                case + 1,
+                self.cases,
+                self.seed,
+                self.seed,
+                reference(&input),
+                optimized(&input),
+            );
+        }
+    }
+}
+
+fn from_env<T: FromStr>(name: &str) -> Option<T> {
+    env::var(name).ok().and_then(|value| value.parse().ok())
+}
+
+/// Cross-checks two implementations with the default settings, see [`CrossCheck::run`].
+pub fn cross_check<T, R>(
+    generate: impl Fn(&mut Rng, usize) -> T,
+    shrink: impl Fn(&T) -> Vec<T>,
+    reference: impl Fn(&T) -> R,
+    optimized: impl Fn(&T) -> R,
+) where
+    T: Debug,
+    R: PartialEq + Debug,
+{
+    CrossCheck::default().run(generate, shrink, reference, optimized);
+}
+
+/// Replaces a failing input with its first failing shrink candidate until none of the candidates fails.
+fn shrink_failure<T>(
+    mut input: T,
+    shrink: impl Fn(&T) -> Vec<T>,
+    fails: impl Fn(&T) -> bool,
+) -> (T, usize) {
+    let mut steps = 0;
+
+    while let Some(smaller) = shrink(&input).into_iter().find(|c| fails(c)) {
+        input = smaller;
+        steps += 1;
+    }
+
+    (input, steps)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{numbers, shrink_failure, shrink_numbers, CrossCheck};
+
+    #[test]
+    fn passes_equal_implementations() {
+        CrossCheck::default().run(
+            |rng, size| numbers(rng, size, 100),
+            |n| shrink_numbers(n),
+            |n| n.iter().sum::<u64>(),
+            |n| n.iter().rev().sum::<u64>(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "cross-check failed")]
+    fn reports_differing_implementations() {
+        CrossCheck::default().run(
+            |rng, size| numbers(rng, size, 100),
+            |n| shrink_numbers(n),
+            |n| n.iter().sum::<u64>(),
+            |n| n.iter().filter(|&&x| x != 42).sum::<u64>(),
+        );
+    }
+
+    #[test]
+    fn shrinks_to_minimal_inputs() {
+        // fails for every list containing a number of at least 10.
+        let (input, steps) = shrink_failure(
+            vec![3, 40, 7, 25, 1],
+            |n| shrink_numbers(n),
+            |n| n.iter().any(|&x| x >= 10),
+        );
+        // numbers are shrunk by halving them, 25 becomes 12 but not 6.
+        assert_eq!(input, vec![12]);
+        assert!(steps > 0);
+    }
+}